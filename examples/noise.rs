@@ -19,16 +19,15 @@ fn main() {
 
     let noise = WhiteNoise.tap();
 
-    let lpf = SinglePoleLpf::lpf(noise.clone(), CUTOFF, context.sample_rate())
-        .simple_lpf(CUTOFF, context.sample_rate())
-        .simple_lpf(CUTOFF, context.sample_rate());
-    let hpf = SinglePoleHpf::hpf(noise.clone(), CUTOFF, context.sample_rate())
-        .simple_hpf(CUTOFF, context.sample_rate())
-        .simple_hpf(CUTOFF, context.sample_rate());
+    let lpf = SinglePoleLpf::lpf(noise.clone(), CUTOFF)
+        .simple_lpf(CUTOFF)
+        .simple_lpf(CUTOFF);
+    let hpf = SinglePoleHpf::hpf(noise.clone(), CUTOFF)
+        .simple_hpf(CUTOFF)
+        .simple_hpf(CUTOFF);
     let pass = noise.clone();
 
-    let switch =
-        Clock::bpm(30.0).sequential_switch([lpf.boxed(), hpf.boxed(), pass.boxed()].into_iter());
+    let switch = Clock::bpm(30.0).sequential_switch([lpf.boxed(), hpf.boxed(), pass.boxed()]);
 
     let synth = switch.mul(0.8);
 
@@ -17,35 +17,38 @@ fn main() {
     let mut context = SynthContext::new(config.sample_rate().0);
 
     // Create a clock for generating triggers.
-    let clock = Clock::bpm(440.0).tap();
+    let clock = Clock::bpm(440.0);
 
-    // Switch through a sequence of "control voltages" using the clock triggers.
-    // These values will modulate the VCO frequecy.
-    let notes = clock.clone().sequential_switch([
-        Const(0.0).boxed(),
-        Const(4.0 * SEMITONE).boxed(),
-        Const(7.0 * SEMITONE).boxed(),
-        Const(4.0 * SEMITONE).boxed(),
-        Const(12.0 * SEMITONE).boxed(),
-        Const(4.0 * SEMITONE).boxed(),
-        Const(10.0 * SEMITONE).boxed(),
-        Const(12.0 * SEMITONE).boxed(),
-        Const(15.0 * SEMITONE).boxed(),
-        Const(7.0 * SEMITONE).boxed(),
-        Const(10.0 * SEMITONE).boxed(),
-        Const(12.0 * SEMITONE).boxed(),
-        Const(17.0 * SEMITONE).boxed(),
-        Const(12.0 * SEMITONE).boxed(),
-        Const(16.0 * SEMITONE).boxed(),
-        Const(16.0 * SEMITONE).boxed(),
-    ]);
+    // Step through a sequence of notes using the clock triggers. Each step
+    // outputs a "control voltage" for the VCO frequency, along with a gate
+    // and a velocity.
+    let [notes, gate, velocity] = clock
+        .step_sequencer([
+            Step::new(0.0),
+            Step::new(4.0 * SEMITONE),
+            Step::new(7.0 * SEMITONE),
+            Step::new(4.0 * SEMITONE).velocity(0.6),
+            Step::new(12.0 * SEMITONE),
+            Step::new(4.0 * SEMITONE).probability(0.5),
+            Step::new(10.0 * SEMITONE),
+            Step::new(12.0 * SEMITONE).slide(),
+            Step::new(15.0 * SEMITONE).gate(1.0),
+            Step::new(7.0 * SEMITONE).slide(),
+            Step::new(10.0 * SEMITONE),
+            Step::new(12.0 * SEMITONE).velocity(0.6),
+            Step::new(17.0 * SEMITONE),
+            Step::new(12.0 * SEMITONE).ratchets(2),
+            Step::new(16.0 * SEMITONE).ratchets(3),
+            Step::new(16.0 * SEMITONE).skip(),
+        ])
+        .split();
 
     // Modulate a VCO, with a base frequency of C4.
     // This gives us our basic 16-step sequencer.
     let sequencer = Saw::oscillator(C4).v_oct(notes);
 
-    // Create an attack/decay envelope, triggered by the same clock source.
-    let envelope = clock.ad_envelope(0.005, 0.1);
+    // Create an envelope from the sequencer gate, scaled by the step velocity.
+    let envelope = gate.adsr_envelope(0.005, 0.1, 0.3, 0.05).mul(velocity);
 
     // Modulate amplitude using the envelope.
     let voice = sequencer.mul(envelope);
@@ -58,8 +61,17 @@ fn main() {
         .add(Sine::oscillator(0.07).mul(0.1));
     let clipped = voice.clip(clip_lfo).tap();
 
-    let delay = clipped.clone().delay(0.227, context.sample_rate());
-    let synth = clipped.mix(delay, 0.3).mul(0.1);
+    // Feed the delay back into itself for a decaying echo.
+    let feedback = FeedbackReturn::new();
+    let echo = clipped
+        .clone()
+        .add(feedback.clone().mul(0.4))
+        .delay(0.227, 0.227)
+        .feedback_send(&feedback);
+    let synth = clipped
+        .mix(echo, 0.3)
+        .reverb(0.6, 0.5, 0.4, 0.01, 0.25)
+        .mul(0.1);
 
     let cpal_out = CpalMono::new(&device, &config);
     let mut sink = Sink::cpal_mono(synth, cpal_out);
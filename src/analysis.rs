@@ -0,0 +1,222 @@
+use std::f32::consts::PI;
+use std::ops;
+
+use crate::sources::Const;
+use crate::sources::Impulse;
+use crate::Operator;
+use crate::SynthContext;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Complex {
+    pub re: f32,
+    pub im: f32,
+}
+
+impl Complex {
+    pub fn new(re: f32, im: f32) -> Self {
+        Self { re, im }
+    }
+
+    pub fn from_polar(magnitude: f32, phase: f32) -> Self {
+        Self {
+            re: magnitude * phase.cos(),
+            im: magnitude * phase.sin(),
+        }
+    }
+
+    pub fn magnitude(self) -> f32 {
+        self.re.hypot(self.im)
+    }
+
+    pub fn phase(self) -> f32 {
+        self.im.atan2(self.re)
+    }
+}
+
+impl ops::Add for Complex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl ops::Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl ops::Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl ops::Mul<f32> for Complex {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self {
+        Self::new(self.re * rhs, self.im * rhs)
+    }
+}
+
+impl ops::Div for Complex {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        let denominator = rhs.re * rhs.re + rhs.im * rhs.im;
+
+        Self::new(
+            (self.re * rhs.re + self.im * rhs.im) / denominator,
+            (self.im * rhs.re - self.re * rhs.im) / denominator,
+        )
+    }
+}
+
+pub fn decibels(magnitude: f32) -> f32 {
+    20.0 * magnitude.log10()
+}
+
+// Evaluates z^-samples on the unit circle at the given frequency.
+pub(crate) fn delay(samples: f32, frequency: f32, sample_rate: u32) -> Complex {
+    Complex::from_polar(1.0, -2.0 * PI * frequency * samples / sample_rate as f32)
+}
+
+// A control input holding a single value, so a filter's response is known without rendering.
+pub trait Constant {
+    fn value(&self) -> f32;
+}
+
+impl Constant for f32 {
+    fn value(&self) -> f32 {
+        *self
+    }
+}
+
+impl Constant for Const {
+    fn value(&self) -> f32 {
+        self.0
+    }
+}
+
+pub trait FrequencyResponse {
+    fn response(&self, frequency: f32, sample_rate: u32) -> Complex;
+
+    fn magnitude(&self, frequency: f32, sample_rate: u32) -> f32 {
+        self.response(frequency, sample_rate).magnitude()
+    }
+
+    fn phase(&self, frequency: f32, sample_rate: u32) -> f32 {
+        self.response(frequency, sample_rate).phase()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Spectrum {
+    bins: Vec<Complex>,
+    sample_rate: u32,
+}
+
+impl Spectrum {
+    pub fn bins(&self) -> &[Complex] {
+        &self.bins[..=self.bins.len() / 2]
+    }
+
+    pub fn bin_frequency(&self, bin: usize) -> f32 {
+        bin as f32 * self.sample_rate as f32 / self.bins.len() as f32
+    }
+
+    pub fn response(&self, frequency: f32) -> Complex {
+        let bins = self.bins();
+        let position = (frequency.max(0.0) * self.bins.len() as f32 / self.sample_rate as f32)
+            .min((bins.len() - 1) as f32);
+
+        let index = (position.floor() as usize).min(bins.len() - 1);
+        let next = (index + 1).min(bins.len() - 1);
+        let factor = position - index as f32;
+
+        bins[index] * (1.0 - factor) + bins[next] * factor
+    }
+
+    pub fn magnitude(&self, frequency: f32) -> f32 {
+        self.response(frequency).magnitude()
+    }
+
+    pub fn phase(&self, frequency: f32) -> f32 {
+        self.response(frequency).phase()
+    }
+}
+
+pub fn impulse_response<F, O>(sample_rate: u32, length: usize, build: F) -> Vec<f32>
+where
+    F: FnOnce(Impulse) -> O,
+    O: Operator,
+{
+    let mut context = SynthContext::new(sample_rate);
+    let mut operator = build(Impulse::new());
+    let mut samples = Vec::with_capacity(length);
+
+    while samples.len() < length {
+        samples.extend(operator.render(&mut context));
+        context.update();
+    }
+
+    samples.truncate(length);
+    samples
+}
+
+pub fn measure_response<F, O>(sample_rate: u32, length: usize, build: F) -> Spectrum
+where
+    F: FnOnce(Impulse) -> O,
+    O: Operator,
+{
+    let length = length.max(2).next_power_of_two();
+    let samples = impulse_response(sample_rate, length, build);
+
+    let mut bins: Vec<_> = samples
+        .into_iter()
+        .map(|sample| Complex::new(sample, 0.0))
+        .collect();
+    fft(&mut bins);
+
+    Spectrum { bins, sample_rate }
+}
+
+fn fft(bins: &mut [Complex]) {
+    let length = bins.len();
+    let bits = length.trailing_zeros();
+
+    for i in 0..length {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+
+        if i < j {
+            bins.swap(i, j);
+        }
+    }
+
+    let mut size = 2;
+
+    while size <= length {
+        for start in (0..length).step_by(size) {
+            for k in 0..size / 2 {
+                let twiddle = Complex::from_polar(1.0, -2.0 * PI * k as f32 / size as f32);
+
+                let even = bins[start + k];
+                let odd = bins[start + k + size / 2] * twiddle;
+
+                bins[start + k] = even + odd;
+                bins[start + k + size / 2] = even - odd;
+            }
+        }
+
+        size *= 2;
+    }
+}
@@ -1,3 +1,4 @@
+use std::cell::Ref;
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -72,6 +73,7 @@ impl<I> Tap<I>
 where
     I: Operator,
 {
+    #[allow(clippy::self_named_constructors)]
     pub fn tap(input: I) -> Self {
         Self {
             inner: Rc::new(RefCell::new(TapInner {
@@ -108,3 +110,130 @@ where
         self.inner.borrow().block
     }
 }
+
+pub trait MultiOperator<const N: usize> {
+    fn render_outputs(&mut self, context: &mut SynthContext) -> [Block; N];
+
+    fn split(self) -> [Split<Self, N>; N]
+    where
+        Self: Sized,
+    {
+        let inner = Rc::new(RefCell::new(SplitInner {
+            operator: self,
+            count: 0,
+            blocks: [Block::silence(); N],
+        }));
+
+        std::array::from_fn(|index| Split {
+            inner: Rc::clone(&inner),
+            index,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Split<M, const N: usize> {
+    inner: Rc<RefCell<SplitInner<M, N>>>,
+    index: usize,
+}
+
+#[derive(Debug)]
+struct SplitInner<M, const N: usize> {
+    operator: M,
+    count: u32,
+    blocks: [Block; N],
+}
+
+impl<M, const N: usize> Split<M, N> {
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+
+    pub(crate) fn operator(&self) -> Ref<'_, M> {
+        Ref::map(self.inner.borrow(), |inner| &inner.operator)
+    }
+}
+
+impl<M, const N: usize> Clone for Split<M, N>
+where
+    M: MultiOperator<N>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+            index: self.index,
+        }
+    }
+}
+
+impl<M, const N: usize> Operator for Split<M, N>
+where
+    M: MultiOperator<N>,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        if self.inner.borrow().count == context.sample_count {
+            let blocks = self.inner.borrow_mut().operator.render_outputs(context);
+            self.inner.borrow_mut().blocks = blocks;
+            self.inner.borrow_mut().count += BLOCK_SIZE as u32;
+        }
+
+        self.inner.borrow().blocks[self.index]
+    }
+}
+
+// The return plays back whatever its send rendered in the previous block, so a loop through
+// a send and its return always has one block of latency. Render the send after the return
+// (for example, by wrapping the whole loop in it) for that latency to stay consistent.
+#[derive(Debug, Clone)]
+pub struct FeedbackReturn {
+    block: Rc<RefCell<Block>>,
+}
+
+impl FeedbackReturn {
+    pub fn new() -> Self {
+        Self {
+            block: Rc::new(RefCell::new(Block::silence())),
+        }
+    }
+}
+
+impl Default for FeedbackReturn {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Operator for FeedbackReturn {
+    fn render(&mut self, _: &mut SynthContext) -> Block {
+        *self.block.borrow()
+    }
+}
+
+#[derive(Debug)]
+pub struct FeedbackSend<I> {
+    input: I,
+    block: Rc<RefCell<Block>>,
+}
+
+impl<I> FeedbackSend<I>
+where
+    I: Operator,
+{
+    pub fn new(input: I, feedback: &FeedbackReturn) -> Self {
+        Self {
+            input,
+            block: Rc::clone(&feedback.block),
+        }
+    }
+}
+
+impl<I> Operator for FeedbackSend<I>
+where
+    I: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let block = self.input.render(context);
+        *self.block.borrow_mut() = block;
+        block
+    }
+}
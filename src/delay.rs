@@ -1,47 +1,147 @@
-use std::collections::VecDeque;
-
 use crate::Block;
 use crate::Operator;
 use crate::SynthContext;
 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    #[default]
+    Linear,
+    Cubic,
+    Allpass,
+}
+
 #[derive(Debug, Clone)]
-pub struct Delay<I> {
+pub struct Delay<I, T> {
     input: I,
-    length: usize,
-    buffer: VecDeque<f32>,
+    time: T,
+    max_time: f32,
+    interpolation: Interpolation,
+    line: DelayLine,
 }
 
-impl<I> Delay<I>
+impl<I, T> Delay<I, T>
 where
     I: Operator,
+    T: Operator,
 {
-    pub(crate) fn delay(input: I, time: f32, sample_rate: u32) -> Self {
-        let length = (time * sample_rate as f32).round() as usize;
-
+    pub fn new(input: I, time: T, max_time: f32) -> Self {
         Self {
             input,
-            length,
-            buffer: VecDeque::with_capacity(length),
+            time,
+            max_time,
+            interpolation: Interpolation::default(),
+            line: DelayLine::new(),
+        }
+    }
+
+    pub fn interpolation(self, interpolation: Interpolation) -> Self {
+        Self {
+            interpolation,
+            ..self
         }
     }
 }
 
-impl<I> Operator for Delay<I>
+impl<I, T> Operator for Delay<I, T>
 where
     I: Operator,
+    T: Operator,
 {
     fn render(&mut self, context: &mut SynthContext) -> Block {
         let input = self.input.render(context);
+        let time = self.time.render(context);
+
+        self.line.allocate(self.max_time, context.sample_rate());
 
         Block::from_sample_fn(|i| {
-            let sample = if self.buffer.len() == self.length {
-                self.buffer.pop_front().unwrap()
-            } else {
-                0.0
-            };
+            let delay = time[i].min(self.max_time).max(0.0) * context.sample_rate() as f32;
+            let sample = self.line.interpolate(delay, self.interpolation);
 
-            self.buffer.push_back(input[i]);
+            self.line.write(input[i]);
             sample
         })
     }
 }
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DelayLine {
+    buffer: Vec<f32>,
+    position: usize,
+    allpass_output: f32,
+}
+
+impl DelayLine {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn allocate(&mut self, max_time: f32, sample_rate: u32) {
+        let length = (max_time.max(0.0) * sample_rate as f32).ceil() as usize + 2;
+
+        if self.buffer.len() != length {
+            self.buffer = vec![0.0; length];
+            self.position = 0;
+            self.allpass_output = 0.0;
+        }
+    }
+
+    pub(crate) fn max_delay(&self) -> f32 {
+        self.buffer.len().saturating_sub(1) as f32
+    }
+
+    pub(crate) fn write(&mut self, sample: f32) {
+        self.buffer[self.position] = sample;
+        self.position = (self.position + 1) % self.buffer.len();
+    }
+
+    pub(crate) fn tap(&self, delay: usize) -> f32 {
+        let length = self.buffer.len();
+        let delay = delay.clamp(1, length);
+
+        self.buffer[(self.position + length - delay) % length]
+    }
+
+    pub(crate) fn read(&self, delay: f32) -> f32 {
+        let delay = delay.clamp(1.0, self.max_delay());
+        let whole = delay.floor();
+        let fract = delay - whole;
+
+        let a = self.tap(whole as usize);
+        let b = self.tap(whole as usize + 1);
+
+        a + (b - a) * fract
+    }
+
+    pub(crate) fn interpolate(&mut self, delay: f32, interpolation: Interpolation) -> f32 {
+        match interpolation {
+            Interpolation::Linear => self.read(delay),
+            Interpolation::Cubic => {
+                let delay = delay.min(self.max_delay() - 1.0).max(1.0);
+                let whole = delay.floor() as usize;
+                let t = delay - whole as f32;
+
+                let x0 = self.tap(whole - 1);
+                let x1 = self.tap(whole);
+                let x2 = self.tap(whole + 1);
+                let x3 = self.tap(whole + 2);
+
+                // 4-point, 3rd-order Hermite interpolation.
+                let c1 = 0.5 * (x2 - x0);
+                let c2 = x0 - 2.5 * x1 + 2.0 * x2 - 0.5 * x3;
+                let c3 = 0.5 * (x3 - x0) + 1.5 * (x1 - x2);
+
+                ((c3 * t + c2) * t + c1) * t + x1
+            }
+            Interpolation::Allpass => {
+                let delay = delay.clamp(1.0, self.max_delay());
+                let whole = delay.floor() as usize;
+                let fract = delay - whole as f32;
+                let coefficient = (1.0 - fract) / (1.0 + fract);
+
+                self.allpass_output = coefficient * self.tap(whole) + self.tap(whole + 1)
+                    - coefficient * self.allpass_output;
+                self.allpass_output
+            }
+        }
+    }
+}
@@ -18,11 +18,11 @@ impl From<f32> for TriggerState {
     }
 }
 
-impl Into<f32> for TriggerState {
-    fn into(self) -> f32 {
-        match self {
-            Self::Low => 0.0,
-            Self::High => 1.0,
+impl From<TriggerState> for f32 {
+    fn from(state: TriggerState) -> Self {
+        match state {
+            TriggerState::Low => 0.0,
+            TriggerState::High => 1.0,
         }
     }
 }
@@ -55,3 +55,82 @@ where
         })
     }
 }
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detection {
+    #[default]
+    Peak,
+    Rms,
+}
+
+pub(crate) fn smoothing_coefficient(time: f32, sample_rate: u32) -> f32 {
+    if time > 0.0 {
+        (-1.0 / (time * sample_rate as f32)).exp()
+    } else {
+        0.0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Follower<I, A, R> {
+    input: I,
+    attack: A,
+    release: R,
+    detection: Detection,
+    envelope: f32,
+}
+
+impl<I, A, R> Follower<I, A, R>
+where
+    I: Operator,
+    A: Operator,
+    R: Operator,
+{
+    pub fn new(input: I, attack: A, release: R) -> Self {
+        Self {
+            input,
+            attack,
+            release,
+            detection: Detection::default(),
+            envelope: 0.0,
+        }
+    }
+
+    pub fn detection(self, detection: Detection) -> Self {
+        Self { detection, ..self }
+    }
+}
+
+impl<I, A, R> Operator for Follower<I, A, R>
+where
+    I: Operator,
+    A: Operator,
+    R: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let attack = self.attack.render(context);
+        let release = self.release.render(context);
+
+        Block::from_sample_fn(|i| {
+            let level = match self.detection {
+                Detection::Peak => input[i].abs(),
+                Detection::Rms => input[i] * input[i],
+            };
+
+            let time = if level > self.envelope {
+                attack[i]
+            } else {
+                release[i]
+            };
+            let coefficient = smoothing_coefficient(time, context.sample_rate());
+
+            self.envelope = level + (self.envelope - level) * coefficient;
+
+            match self.detection {
+                Detection::Peak => self.envelope,
+                Detection::Rms => self.envelope.sqrt(),
+            }
+        })
+    }
+}
@@ -0,0 +1,146 @@
+use crate::Block;
+use crate::Operator;
+use crate::SynthContext;
+
+const MAX_FOLDS: f32 = 4.0;
+const MAX_BITS: f32 = 24.0;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Saturation {
+    #[default]
+    Tanh,
+    Cubic,
+    Diode,
+}
+
+impl Saturation {
+    pub fn shape(self, sample: f32) -> f32 {
+        match self {
+            Saturation::Tanh => sample.tanh(),
+            Saturation::Cubic => {
+                let sample = sample.clamp(-1.0, 1.0);
+                1.5 * sample - 0.5 * sample.powi(3)
+            }
+            // Clips the negative half harder than the positive half, which adds even
+            // harmonics and some DC offset.
+            Saturation::Diode => {
+                if sample >= 0.0 {
+                    sample.tanh()
+                } else {
+                    0.5 * (2.0 * sample).tanh()
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Saturate<I, Cv> {
+    pub input: I,
+    pub drive: Cv,
+    pub curve: Saturation,
+}
+
+impl<I, Cv> Operator for Saturate<I, Cv>
+where
+    I: Operator,
+    Cv: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let drive = self.drive.render(context);
+
+        Block::from_sample_fn(|i| self.curve.shape(input[i] * drive[i].max(0.0)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Fold<I, Cv> {
+    pub input: I,
+    pub amount: Cv,
+}
+
+impl<I, Cv> Operator for Fold<I, Cv>
+where
+    I: Operator,
+    Cv: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let amount = self.amount.render(context);
+
+        Block::from_sample_fn(|i| {
+            let gain = 1.0 + amount[i].clamp(0.0, 1.0) * MAX_FOLDS;
+            let sample = input[i] * gain;
+
+            // Reflects the signal back into [-1, 1] each time it crosses either edge.
+            1.0 - ((sample + 1.0).rem_euclid(4.0) - 2.0).abs()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bitcrush<I, Cv> {
+    pub input: I,
+    pub bits: Cv,
+}
+
+impl<I, Cv> Operator for Bitcrush<I, Cv>
+where
+    I: Operator,
+    Cv: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let bits = self.bits.render(context);
+
+        Block::from_sample_fn(|i| {
+            let steps = 2_f32.powf(bits[i].clamp(1.0, MAX_BITS) - 1.0);
+            (input[i] * steps).round() / steps
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Decimate<I, Cv> {
+    input: I,
+    rate: Cv,
+    phase: f32,
+    held: f32,
+}
+
+impl<I, Cv> Decimate<I, Cv>
+where
+    I: Operator,
+    Cv: Operator,
+{
+    pub fn new(input: I, rate: Cv) -> Self {
+        Self {
+            input,
+            rate,
+            phase: 1.0,
+            held: 0.0,
+        }
+    }
+}
+
+impl<I, Cv> Operator for Decimate<I, Cv>
+where
+    I: Operator,
+    Cv: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let rate = self.rate.render(context);
+
+        Block::from_sample_fn(|i| {
+            if self.phase >= 1.0 {
+                self.phase -= self.phase.floor();
+                self.held = input[i];
+            }
+
+            self.phase += rate[i].max(0.0) * context.sample_time();
+            self.held
+        })
+    }
+}
@@ -0,0 +1,476 @@
+use crate::analysis::decibels;
+use crate::branch::MultiOperator;
+use crate::delay::DelayLine;
+use crate::detect::smoothing_coefficient;
+use crate::sources::Const;
+use crate::sources::Silence;
+use crate::Block;
+use crate::Operator;
+use crate::SynthContext;
+
+const LOOKAHEAD: f32 = 0.005;
+
+pub(crate) fn linear(decibels: f32) -> f32 {
+    10_f32.powf(decibels / 20.0)
+}
+
+pub(crate) fn level(sample: f32) -> f32 {
+    decibels(sample.abs().max(1e-9))
+}
+
+// Static gain curve with a quadratic soft knee, returning the gain change in decibels.
+fn compression_gain(level: f32, threshold: f32, ratio: f32, knee: f32) -> f32 {
+    let ratio = ratio.max(1.0);
+    let knee = knee.max(0.0);
+    let overshoot = level - threshold;
+
+    // A zero knee must fall through to the hard curve, since the soft one divides by it.
+    let output = if 2.0 * overshoot < -knee {
+        level
+    } else if knee > 0.0 && 2.0 * overshoot.abs() <= knee {
+        level + (1.0 / ratio - 1.0) * (overshoot + knee / 2.0).powi(2) / (2.0 * knee)
+    } else {
+        threshold + overshoot / ratio
+    };
+
+    output - level
+}
+
+#[derive(Debug, Clone)]
+pub struct Compressor<I, T, R, A, Rl, K, M, S> {
+    input: I,
+    threshold: T,
+    ratio: R,
+    attack: A,
+    release: Rl,
+    knee: K,
+    makeup: M,
+    sidechain: S,
+    sidechained: bool,
+    reduction: f32,
+}
+
+impl<I, T, R, A, Rl> Compressor<I, T, R, A, Rl, Const, Const, Silence>
+where
+    I: Operator,
+    T: Operator,
+    R: Operator,
+    A: Operator,
+    Rl: Operator,
+{
+    pub fn new(input: I, threshold: T, ratio: R, attack: A, release: Rl) -> Self {
+        Self {
+            input,
+            threshold,
+            ratio,
+            attack,
+            release,
+            knee: Const(0.0),
+            makeup: Const(0.0),
+            sidechain: Silence,
+            sidechained: false,
+            reduction: 0.0,
+        }
+    }
+}
+
+impl<I, T, R, A, Rl, K, M, S> Compressor<I, T, R, A, Rl, K, M, S> {
+    pub fn knee<Cv>(self, knee: Cv) -> Compressor<I, T, R, A, Rl, Cv, M, S>
+    where
+        Cv: Operator,
+    {
+        Compressor {
+            input: self.input,
+            threshold: self.threshold,
+            ratio: self.ratio,
+            attack: self.attack,
+            release: self.release,
+            knee,
+            makeup: self.makeup,
+            sidechain: self.sidechain,
+            sidechained: self.sidechained,
+            reduction: self.reduction,
+        }
+    }
+
+    pub fn makeup<Cv>(self, makeup: Cv) -> Compressor<I, T, R, A, Rl, K, Cv, S>
+    where
+        Cv: Operator,
+    {
+        Compressor {
+            input: self.input,
+            threshold: self.threshold,
+            ratio: self.ratio,
+            attack: self.attack,
+            release: self.release,
+            knee: self.knee,
+            makeup,
+            sidechain: self.sidechain,
+            sidechained: self.sidechained,
+            reduction: self.reduction,
+        }
+    }
+
+    pub fn sidechain<Sc>(self, sidechain: Sc) -> Compressor<I, T, R, A, Rl, K, M, Sc>
+    where
+        Sc: Operator,
+    {
+        Compressor {
+            input: self.input,
+            threshold: self.threshold,
+            ratio: self.ratio,
+            attack: self.attack,
+            release: self.release,
+            knee: self.knee,
+            makeup: self.makeup,
+            sidechain,
+            sidechained: true,
+            reduction: self.reduction,
+        }
+    }
+}
+
+impl<I, T, R, A, Rl, K, M, S> Operator for Compressor<I, T, R, A, Rl, K, M, S>
+where
+    I: Operator,
+    T: Operator,
+    R: Operator,
+    A: Operator,
+    Rl: Operator,
+    K: Operator,
+    M: Operator,
+    S: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let threshold = self.threshold.render(context);
+        let ratio = self.ratio.render(context);
+        let attack = self.attack.render(context);
+        let release = self.release.render(context);
+        let knee = self.knee.render(context);
+        let makeup = self.makeup.render(context);
+        let sidechain = self.sidechain.render(context);
+
+        let key = if self.sidechained { sidechain } else { input };
+
+        Block::from_sample_fn(|i| {
+            let target = compression_gain(level(key[i]), threshold[i], ratio[i], knee[i].max(0.0));
+
+            let time = if target < self.reduction {
+                attack[i]
+            } else {
+                release[i]
+            };
+            let coefficient = smoothing_coefficient(time, context.sample_rate());
+
+            self.reduction = target + (self.reduction - target) * coefficient;
+            input[i] * linear(self.reduction + makeup[i])
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Limiter<I, C, R> {
+    input: I,
+    ceiling: C,
+    release: R,
+    line: DelayLine,
+    gains: DelayLine,
+    gain: f32,
+}
+
+impl<I, C, R> Limiter<I, C, R>
+where
+    I: Operator,
+    C: Operator,
+    R: Operator,
+{
+    pub fn new(input: I, ceiling: C, release: R) -> Self {
+        Self {
+            input,
+            ceiling,
+            release,
+            line: DelayLine::new(),
+            gains: DelayLine::new(),
+            gain: 1.0,
+        }
+    }
+}
+
+impl<I, C, R> Operator for Limiter<I, C, R>
+where
+    I: Operator,
+    C: Operator,
+    R: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let ceiling = self.ceiling.render(context);
+        let release = self.release.render(context);
+
+        let sample_rate = context.sample_rate();
+        let lookahead = (LOOKAHEAD * sample_rate as f32).round().max(1.0) as usize;
+
+        self.line.allocate(LOOKAHEAD, sample_rate);
+        self.gains.allocate(LOOKAHEAD, sample_rate);
+
+        let attack = smoothing_coefficient(LOOKAHEAD / 5.0, sample_rate);
+
+        Block::from_sample_fn(|i| {
+            let ceiling = linear(ceiling[i]);
+            let required = (ceiling / input[i].abs().max(1e-9)).min(1.0);
+
+            self.gains.write(required);
+            self.line.write(input[i]);
+
+            // Gains for every sample between the delayed output and the newest input, so the
+            // gain starts falling before a peak reaches the output.
+            let target = (1..=lookahead)
+                .map(|delay| self.gains.tap(delay))
+                .fold(1.0, f32::min);
+
+            let coefficient = if target < self.gain {
+                attack
+            } else {
+                smoothing_coefficient(release[i], sample_rate)
+            };
+            self.gain = target + (self.gain - target) * coefficient;
+
+            let output = self.line.tap(lookahead) * self.gain;
+            output.clamp(-ceiling, ceiling)
+        })
+    }
+}
+
+const GATE_RANGE: f32 = -80.0;
+const GATE_HYSTERESIS: f32 = 3.0;
+const DETECTOR_RELEASE: f32 = 0.005;
+
+// Gain change below the threshold; an infinite ratio closes the gate fully.
+fn expansion_gain(level: f32, threshold: f32, ratio: f32) -> f32 {
+    let undershoot = level - threshold;
+
+    if undershoot >= 0.0 {
+        0.0
+    } else {
+        (undershoot * (ratio.max(1.0) - 1.0)).max(GATE_RANGE)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NoiseGate<I, T, A, H, R, Hy, Ra, S> {
+    input: I,
+    threshold: T,
+    attack: A,
+    hold: H,
+    release: R,
+    hysteresis: Hy,
+    ratio: Ra,
+    sidechain: S,
+    sidechained: bool,
+    open: bool,
+    held: f32,
+    envelope: f32,
+    gain: f32,
+}
+
+impl<I, T, A, H, R> NoiseGate<I, T, A, H, R, Const, Const, Silence>
+where
+    I: Operator,
+    T: Operator,
+    A: Operator,
+    H: Operator,
+    R: Operator,
+{
+    pub fn new(input: I, threshold: T, attack: A, hold: H, release: R) -> Self {
+        Self {
+            input,
+            threshold,
+            attack,
+            hold,
+            release,
+            hysteresis: Const(GATE_HYSTERESIS),
+            ratio: Const(f32::INFINITY),
+            sidechain: Silence,
+            sidechained: false,
+            open: false,
+            held: 0.0,
+            envelope: 0.0,
+            gain: GATE_RANGE,
+        }
+    }
+}
+
+impl<I, T, A, H, R, Hy, Ra, S> NoiseGate<I, T, A, H, R, Hy, Ra, S> {
+    pub fn hysteresis<Cv>(self, hysteresis: Cv) -> NoiseGate<I, T, A, H, R, Cv, Ra, S>
+    where
+        Cv: Operator,
+    {
+        NoiseGate {
+            input: self.input,
+            threshold: self.threshold,
+            attack: self.attack,
+            hold: self.hold,
+            release: self.release,
+            hysteresis,
+            ratio: self.ratio,
+            sidechain: self.sidechain,
+            sidechained: self.sidechained,
+            open: self.open,
+            held: self.held,
+            envelope: self.envelope,
+            gain: self.gain,
+        }
+    }
+
+    // Turns the gate into a downward expander with the given ratio.
+    pub fn ratio<Cv>(self, ratio: Cv) -> NoiseGate<I, T, A, H, R, Hy, Cv, S>
+    where
+        Cv: Operator,
+    {
+        NoiseGate {
+            input: self.input,
+            threshold: self.threshold,
+            attack: self.attack,
+            hold: self.hold,
+            release: self.release,
+            hysteresis: self.hysteresis,
+            ratio,
+            sidechain: self.sidechain,
+            sidechained: self.sidechained,
+            open: self.open,
+            held: self.held,
+            envelope: self.envelope,
+            gain: self.gain,
+        }
+    }
+
+    pub fn sidechain<Sc>(self, sidechain: Sc) -> NoiseGate<I, T, A, H, R, Hy, Ra, Sc>
+    where
+        Sc: Operator,
+    {
+        NoiseGate {
+            input: self.input,
+            threshold: self.threshold,
+            attack: self.attack,
+            hold: self.hold,
+            release: self.release,
+            hysteresis: self.hysteresis,
+            ratio: self.ratio,
+            sidechain,
+            sidechained: true,
+            open: self.open,
+            held: self.held,
+            envelope: self.envelope,
+            gain: self.gain,
+        }
+    }
+}
+
+impl<I, T, A, H, R, Hy, Ra, S> MultiOperator<2> for NoiseGate<I, T, A, H, R, Hy, Ra, S>
+where
+    I: Operator,
+    T: Operator,
+    A: Operator,
+    H: Operator,
+    R: Operator,
+    Hy: Operator,
+    Ra: Operator,
+    S: Operator,
+{
+    fn render_outputs(&mut self, context: &mut SynthContext) -> [Block; 2] {
+        let input = self.input.render(context);
+        let threshold = self.threshold.render(context);
+        let attack = self.attack.render(context);
+        let hold = self.hold.render(context);
+        let release = self.release.render(context);
+        let hysteresis = self.hysteresis.render(context);
+        let ratio = self.ratio.render(context);
+        let sidechain = self.sidechain.render(context);
+
+        let key = if self.sidechained { sidechain } else { input };
+        let detector = smoothing_coefficient(DETECTOR_RELEASE, context.sample_rate());
+
+        let mut gate = Block::silence();
+
+        let output = Block::from_sample_fn(|i| {
+            self.envelope = key[i].abs().max(self.envelope * detector);
+            let level = level(self.envelope);
+
+            let close = threshold[i] - hysteresis[i].max(0.0);
+
+            if level > threshold[i] {
+                self.open = true;
+            }
+
+            if level >= close {
+                self.held = 0.0;
+            } else if self.open {
+                self.held += context.sample_time();
+
+                if self.held >= hold[i] {
+                    self.open = false;
+                }
+            }
+
+            let target = if self.open {
+                0.0
+            } else {
+                expansion_gain(level, threshold[i], ratio[i])
+            };
+
+            let time = if target > self.gain {
+                attack[i]
+            } else {
+                release[i]
+            };
+            let coefficient = smoothing_coefficient(time, context.sample_rate());
+            self.gain = target + (self.gain - target) * coefficient;
+
+            gate[i] = if self.open { 1.0 } else { 0.0 };
+            input[i] * linear(self.gain)
+        });
+
+        [output, gate]
+    }
+}
+
+impl<I, T, A, H, R, Hy, Ra, S> Operator for NoiseGate<I, T, A, H, R, Hy, Ra, S>
+where
+    I: Operator,
+    T: Operator,
+    A: Operator,
+    H: Operator,
+    R: Operator,
+    Hy: Operator,
+    Ra: Operator,
+    S: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let [output, _] = self.render_outputs(context);
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::impulse_response;
+    use crate::Const;
+    use crate::OperatorExt;
+    use crate::Square;
+
+    #[test]
+    fn hard_knee_at_threshold_stays_finite() {
+        let square = impulse_response(48_000, 4096, |_| {
+            Square::oscillator(100.0).compress(0.0, 4.0, 0.01, 0.1)
+        });
+        let constant =
+            impulse_response(48_000, 4096, |_| Const(0.1).compress(-20.0, 4.0, 0.01, 0.1));
+
+        assert!(square
+            .iter()
+            .chain(&constant)
+            .all(|sample| sample.is_finite()));
+        assert!(square.iter().all(|sample| sample.abs() == 1.0));
+    }
+}
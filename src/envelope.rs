@@ -1,17 +1,65 @@
+use crate::branch::MultiOperator;
 use crate::detect::Trigger;
 use crate::detect::TriggerState;
+use crate::sources::Const;
 use crate::Block;
 use crate::Lerp;
 use crate::Operator;
 use crate::OperatorExt;
 use crate::SynthContext;
 
+const CURVATURE: f32 = 5.0;
+
+// Exponential segments move quickly at first and then ease into their target, like an RC
+// circuit charging or discharging. Logarithmic segments start slowly and accelerate.
+// `Bend` takes a continuous curvature, where positive values bend towards exponential.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum Curve {
+    #[default]
+    Linear,
+    Exponential,
+    Logarithmic,
+    Bend(f32),
+}
+
+impl Curve {
+    pub fn shape(self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+
+        let curvature = match self {
+            Curve::Linear => 0.0,
+            Curve::Exponential => CURVATURE,
+            Curve::Logarithmic => -CURVATURE,
+            Curve::Bend(curvature) => curvature,
+        };
+
+        if curvature.abs() < 1e-3 {
+            progress
+        } else {
+            (1.0 - (-curvature * progress).exp()) / (1.0 - (-curvature).exp())
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Retrigger {
+    #[default]
+    Reset,
+    Restart,
+    Legato,
+}
+
 #[derive(Debug, Clone)]
 pub struct Ad<A, D, T> {
     attack: A,
     decay: D,
     trigger: Trigger<T>,
     time: Option<f32>,
+    attack_curve: Curve,
+    decay_curve: Curve,
+    retrigger: Retrigger,
+    start_level: f32,
+    level: f32,
 }
 
 pub fn ad<A, D, T>(trigger: T, attack: A, decay: D) -> Ad<A, D, T>
@@ -25,51 +73,571 @@ where
         decay,
         trigger: trigger.trigger(),
         time: None,
+        attack_curve: Curve::default(),
+        decay_curve: Curve::default(),
+        retrigger: Retrigger::default(),
+        start_level: 0.0,
+        level: 0.0,
     }
 }
 
-impl<A, D, T> Operator for Ad<A, D, T>
+impl<A, D, T> Ad<A, D, T> {
+    pub fn attack_curve(self, attack_curve: Curve) -> Self {
+        Self {
+            attack_curve,
+            ..self
+        }
+    }
+
+    pub fn decay_curve(self, decay_curve: Curve) -> Self {
+        Self {
+            decay_curve,
+            ..self
+        }
+    }
+
+    pub fn retrigger(self, retrigger: Retrigger) -> Self {
+        Self { retrigger, ..self }
+    }
+
+    fn start(&mut self) {
+        match self.retrigger {
+            Retrigger::Reset => self.start_level = 0.0,
+            Retrigger::Restart => self.start_level = self.level,
+            Retrigger::Legato if self.time.is_some() => return,
+            Retrigger::Legato => self.start_level = 0.0,
+        }
+
+        self.time = Some(0.0);
+    }
+}
+
+impl<A, D, T> MultiOperator<2> for Ad<A, D, T>
 where
     A: Operator,
     D: Operator,
     T: Operator,
 {
-    fn render(&mut self, context: &mut SynthContext) -> Block {
+    fn render_outputs(&mut self, context: &mut SynthContext) -> [Block; 2] {
         let attack = self.attack.render(context);
         let decay = self.decay.render(context);
         let trigger = self.trigger.render(context);
 
-        Block::from_sample_fn(|i| {
+        let mut end_of_cycle = Block::silence();
+
+        let envelope = Block::from_sample_fn(|i| {
             let attack = attack[i].max(0.0);
             let decay = decay[i].max(0.0);
             let trigger: TriggerState = trigger[i].max(0.0).into();
 
             if trigger == TriggerState::High {
-                self.time = Some(0.0)
+                self.start();
             }
 
-            if let Some(time) = self.time.as_mut() {
+            // Restarting from a non-zero level shortens the attack so its rate is unchanged.
+            let attack = attack * (1.0 - self.start_level);
+
+            self.level = if let Some(time) = self.time.as_mut() {
                 if *time < attack {
                     let progress = *time / attack;
-                    let sample = 0.0.lerp(1.0, progress);
+                    let sample = self
+                        .start_level
+                        .lerp(1.0, self.attack_curve.shape(progress));
 
                     *time += context.sample_time();
 
                     sample
                 } else if *time < attack + decay {
                     let progress = (*time - attack) / decay;
-                    let sample = 1.0.lerp(0.0, progress);
+                    let sample = 1.0.lerp(0.0, self.decay_curve.shape(progress));
 
                     *time += context.sample_time();
 
                     sample
                 } else {
                     self.time = None;
+                    end_of_cycle[i] = 1.0;
                     0.0
                 }
             } else {
                 0.0
+            };
+
+            self.level
+        });
+
+        [envelope, end_of_cycle]
+    }
+}
+
+impl<A, D, T> Operator for Ad<A, D, T>
+where
+    A: Operator,
+    D: Operator,
+    T: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let [envelope, _] = self.render_outputs(context);
+        envelope
+    }
+}
+
+fn progress(time: f32, duration: f32) -> f32 {
+    if duration > 0.0 {
+        (time / duration).min(1.0)
+    } else {
+        1.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AdsrStage {
+    Idle,
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+}
+
+#[derive(Debug, Clone)]
+pub struct Adsr<A, D, S, R, G> {
+    attack: A,
+    decay: D,
+    sustain: S,
+    release: R,
+    gate: G,
+    previous_gate: TriggerState,
+    stage: AdsrStage,
+    time: f32,
+    start_level: f32,
+    level: f32,
+    attack_curve: Curve,
+    decay_curve: Curve,
+    release_curve: Curve,
+}
+
+pub fn adsr<A, D, S, R, G>(
+    gate: G,
+    attack: A,
+    decay: D,
+    sustain: S,
+    release: R,
+) -> Adsr<A, D, S, R, G>
+where
+    A: Operator,
+    D: Operator,
+    S: Operator,
+    R: Operator,
+    G: Operator,
+{
+    Adsr {
+        attack,
+        decay,
+        sustain,
+        release,
+        gate,
+        previous_gate: TriggerState::Low,
+        stage: AdsrStage::Idle,
+        time: 0.0,
+        start_level: 0.0,
+        level: 0.0,
+        attack_curve: Curve::default(),
+        decay_curve: Curve::default(),
+        release_curve: Curve::default(),
+    }
+}
+
+impl<A, D, S, R, G> Adsr<A, D, S, R, G> {
+    pub fn attack_curve(self, attack_curve: Curve) -> Self {
+        Self {
+            attack_curve,
+            ..self
+        }
+    }
+
+    pub fn decay_curve(self, decay_curve: Curve) -> Self {
+        Self {
+            decay_curve,
+            ..self
+        }
+    }
+
+    pub fn release_curve(self, release_curve: Curve) -> Self {
+        Self {
+            release_curve,
+            ..self
+        }
+    }
+
+    fn enter(&mut self, stage: AdsrStage) {
+        self.stage = stage;
+        self.time = 0.0;
+        self.start_level = self.level;
+    }
+}
+
+impl<A, D, S, R, G> Operator for Adsr<A, D, S, R, G>
+where
+    A: Operator,
+    D: Operator,
+    S: Operator,
+    R: Operator,
+    G: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let attack = self.attack.render(context);
+        let decay = self.decay.render(context);
+        let sustain = self.sustain.render(context);
+        let release = self.release.render(context);
+        let gate = self.gate.render(context);
+
+        Block::from_sample_fn(|i| {
+            let attack = attack[i].max(0.0);
+            let decay = decay[i].max(0.0);
+            let sustain = sustain[i].clamp(0.0, 1.0);
+            let release = release[i].max(0.0);
+            let gate: TriggerState = gate[i].into();
+
+            match (self.previous_gate, gate) {
+                (TriggerState::Low, TriggerState::High) => self.enter(AdsrStage::Attack),
+                (TriggerState::High, TriggerState::Low) => self.enter(AdsrStage::Release),
+                _ => {}
+            }
+
+            self.previous_gate = gate;
+
+            match self.stage {
+                AdsrStage::Idle => {
+                    self.level = 0.0;
+                }
+                AdsrStage::Attack => {
+                    let progress = progress(self.time, attack);
+                    self.level = self
+                        .start_level
+                        .lerp(1.0, self.attack_curve.shape(progress));
+
+                    if progress >= 1.0 {
+                        self.enter(AdsrStage::Decay);
+                    }
+                }
+                AdsrStage::Decay => {
+                    let progress = progress(self.time, decay);
+                    self.level = 1.0.lerp(sustain, self.decay_curve.shape(progress));
+
+                    if progress >= 1.0 {
+                        self.enter(AdsrStage::Sustain);
+                    }
+                }
+                AdsrStage::Sustain => {
+                    self.level = sustain;
+                }
+                AdsrStage::Release => {
+                    let progress = progress(self.time, release);
+                    self.level = self
+                        .start_level
+                        .lerp(0.0, self.release_curve.shape(progress));
+
+                    if progress >= 1.0 {
+                        self.enter(AdsrStage::Idle);
+                    }
+                }
             }
+
+            self.time += context.sample_time();
+            self.level
         })
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Breakpoint {
+    pub time: f32,
+    pub level: f32,
+    pub curve: Curve,
+}
+
+impl Breakpoint {
+    pub fn new(time: f32, level: f32, curve: Curve) -> Self {
+        Self { time, level, curve }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Mseg<G> {
+    gate: G,
+    breakpoints: Vec<Breakpoint>,
+    sustain: Option<usize>,
+    loop_points: Option<(usize, usize)>,
+    previous_gate: TriggerState,
+    segment: Option<usize>,
+    time: f32,
+    start_level: f32,
+    level: f32,
+}
+
+pub fn mseg<G>(gate: G, breakpoints: impl IntoIterator<Item = Breakpoint>) -> Mseg<G>
+where
+    G: Operator,
+{
+    Mseg {
+        gate,
+        breakpoints: breakpoints.into_iter().collect(),
+        sustain: None,
+        loop_points: None,
+        previous_gate: TriggerState::Low,
+        segment: None,
+        time: 0.0,
+        start_level: 0.0,
+        level: 0.0,
+    }
+}
+
+impl Mseg<Const> {
+    pub fn free_running(breakpoints: impl IntoIterator<Item = Breakpoint>) -> Self {
+        let mseg = mseg(Const(1.0), breakpoints);
+        let end = mseg.breakpoints.len().saturating_sub(1);
+
+        mseg.loop_points(0, end)
+    }
+}
+
+impl<G> Mseg<G> {
+    pub fn sustain(self, index: usize) -> Self {
+        assert!(index < self.breakpoints.len(), "sustain point out of range");
+
+        Self {
+            sustain: Some(index),
+            ..self
+        }
+    }
+
+    pub fn loop_points(self, start: usize, end: usize) -> Self {
+        assert!(
+            start <= end && end < self.breakpoints.len().max(1),
+            "loop points out of range"
+        );
+
+        Self {
+            loop_points: Some((start, end)),
+            ..self
+        }
+    }
+
+    fn enter(&mut self, segment: Option<usize>) {
+        self.segment = segment;
+        self.time = 0.0;
+        self.start_level = self.level;
+    }
+
+    fn next_segment(&self, segment: usize, held: bool) -> Option<usize> {
+        match self.loop_points {
+            Some((start, end)) if segment == end && held => Some(start),
+            _ if segment + 1 < self.breakpoints.len() => Some(segment + 1),
+            _ => None,
+        }
+    }
+}
+
+impl<G> Operator for Mseg<G>
+where
+    G: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let gate = self.gate.render(context);
+
+        Block::from_sample_fn(|i| {
+            let gate: TriggerState = gate[i].into();
+            let held = gate == TriggerState::High;
+
+            if self.previous_gate == TriggerState::Low && held && !self.breakpoints.is_empty() {
+                self.enter(Some(0));
+            }
+
+            self.previous_gate = gate;
+
+            let Some(segment) = self.segment else {
+                return self.level;
+            };
+
+            let breakpoint = self.breakpoints[segment];
+            let progress = progress(self.time, breakpoint.time.max(0.0));
+            self.level = self
+                .start_level
+                .lerp(breakpoint.level, breakpoint.curve.shape(progress));
+
+            if progress < 1.0 {
+                self.time += context.sample_time();
+            } else if !(self.sustain == Some(segment) && held) {
+                self.enter(self.next_segment(segment, held));
+            }
+
+            self.level
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FunctionStage {
+    Idle,
+    Rise,
+    Fall,
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionGenerator<T, R, F, S, C> {
+    trigger: Trigger<T>,
+    rise: R,
+    fall: F,
+    shape: S,
+    cycle: C,
+    stage: FunctionStage,
+    time: f32,
+    start_level: f32,
+    level: f32,
+}
+
+pub fn function_generator<T, R, F>(
+    trigger: T,
+    rise: R,
+    fall: F,
+) -> FunctionGenerator<T, R, F, Const, Const>
+where
+    T: Operator,
+    R: Operator,
+    F: Operator,
+{
+    FunctionGenerator {
+        trigger: trigger.trigger(),
+        rise,
+        fall,
+        shape: Const(0.0),
+        cycle: Const(0.0),
+        stage: FunctionStage::Idle,
+        time: 0.0,
+        start_level: 0.0,
+        level: 0.0,
+    }
+}
+
+impl<T, R, F, S, C> FunctionGenerator<T, R, F, S, C> {
+    pub fn shape<Cv>(self, shape: Cv) -> FunctionGenerator<T, R, F, Cv, C>
+    where
+        Cv: Operator,
+    {
+        FunctionGenerator {
+            trigger: self.trigger,
+            rise: self.rise,
+            fall: self.fall,
+            shape,
+            cycle: self.cycle,
+            stage: self.stage,
+            time: self.time,
+            start_level: self.start_level,
+            level: self.level,
+        }
+    }
+
+    pub fn cycle<Cv>(self, cycle: Cv) -> FunctionGenerator<T, R, F, S, Cv>
+    where
+        Cv: Operator,
+    {
+        FunctionGenerator {
+            trigger: self.trigger,
+            rise: self.rise,
+            fall: self.fall,
+            shape: self.shape,
+            cycle,
+            stage: self.stage,
+            time: self.time,
+            start_level: self.start_level,
+            level: self.level,
+        }
+    }
+
+    fn enter(&mut self, stage: FunctionStage) {
+        self.stage = stage;
+        self.time = 0.0;
+        self.start_level = self.level;
+    }
+}
+
+impl<T, R, F, S, C> MultiOperator<3> for FunctionGenerator<T, R, F, S, C>
+where
+    T: Operator,
+    R: Operator,
+    F: Operator,
+    S: Operator,
+    C: Operator,
+{
+    fn render_outputs(&mut self, context: &mut SynthContext) -> [Block; 3] {
+        let trigger = self.trigger.render(context);
+        let rise = self.rise.render(context);
+        let fall = self.fall.render(context);
+        let shape = self.shape.render(context);
+        let cycle = self.cycle.render(context);
+
+        let mut end_of_rise = Block::silence();
+        let mut end_of_cycle = Block::silence();
+
+        let output = Block::from_sample_fn(|i| {
+            let trigger: TriggerState = trigger[i].into();
+            let cycle: TriggerState = cycle[i].into();
+            let curve = Curve::Bend(shape[i]);
+
+            // Like Maths, a rise ignores new triggers while a fall can be retriggered.
+            if self.stage != FunctionStage::Rise
+                && (trigger == TriggerState::High
+                    || (self.stage == FunctionStage::Idle && cycle == TriggerState::High))
+            {
+                self.enter(FunctionStage::Rise);
+            }
+
+            match self.stage {
+                FunctionStage::Idle => {}
+                FunctionStage::Rise => {
+                    let rise = rise[i].max(0.0) * (1.0 - self.start_level);
+                    let progress = progress(self.time, rise);
+                    self.level = self.start_level.lerp(1.0, curve.shape(progress));
+
+                    if progress >= 1.0 {
+                        end_of_rise[i] = 1.0;
+                        self.enter(FunctionStage::Fall);
+                    }
+                }
+                FunctionStage::Fall => {
+                    let progress = progress(self.time, fall[i].max(0.0));
+                    self.level = self.start_level.lerp(0.0, curve.shape(progress));
+
+                    if progress >= 1.0 {
+                        end_of_cycle[i] = 1.0;
+
+                        if cycle == TriggerState::High {
+                            self.enter(FunctionStage::Rise);
+                        } else {
+                            self.enter(FunctionStage::Idle);
+                        }
+                    }
+                }
+            }
+
+            self.time += context.sample_time();
+            self.level
+        });
+
+        [output, end_of_rise, end_of_cycle]
+    }
+}
+
+impl<T, R, F, S, C> Operator for FunctionGenerator<T, R, F, S, C>
+where
+    T: Operator,
+    R: Operator,
+    F: Operator,
+    S: Operator,
+    C: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let [output, _, _] = self.render_outputs(context);
+        output
+    }
+}
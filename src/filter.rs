@@ -1,46 +1,69 @@
 use std::f32::consts::E;
 use std::f32::consts::PI;
 
+use crate::analysis;
+use crate::analysis::Complex;
+use crate::analysis::Constant;
+use crate::analysis::FrequencyResponse;
+use crate::branch::MultiOperator;
+use crate::branch::Split;
+use crate::delay::DelayLine;
 use crate::Block;
+use crate::Lerp;
 use crate::Operator;
 use crate::SynthContext;
+use crate::BLOCK_SIZE;
+
+fn normalised_cutoff(cutoff: f32, sample_rate: u32) -> f32 {
+    (cutoff.max(0.0) / sample_rate as f32).min(0.5)
+}
+
+fn lowpass_decay(cutoff: f32, sample_rate: u32) -> f32 {
+    E.powf(-2.0 * PI * normalised_cutoff(cutoff, sample_rate))
+}
+
+fn highpass_decay(cutoff: f32, sample_rate: u32) -> f32 {
+    0.0 - E.powf(-2.0 * PI * (0.5 - normalised_cutoff(cutoff, sample_rate)))
+}
 
 #[derive(Debug, Clone, Copy)]
-pub struct SinglePoleLpf<I> {
+pub struct SinglePoleLpf<I, Cv> {
     input: I,
-    decay: f32,
+    cutoff: Cv,
     buffer: f32,
 }
 
-impl<I> SinglePoleLpf<I>
+impl<I, Cv> SinglePoleLpf<I, Cv>
 where
     I: Operator,
+    Cv: Operator,
 {
-    pub fn lpf(input: I, cutoff: f32, sample_rate: u32) -> Self {
-        let fc = (cutoff / sample_rate as f32).min(0.5);
-        let decay = E.powf(-2.0 * PI * fc);
-
+    pub fn lpf(input: I, cutoff: Cv) -> Self {
         Self {
             input,
-            decay,
+            cutoff,
             buffer: 0.0,
         }
     }
 }
 
-impl<I> Operator for SinglePoleLpf<I>
+impl<I, Cv> Operator for SinglePoleLpf<I, Cv>
 where
     I: Operator,
+    Cv: Operator,
 {
     fn render(&mut self, context: &mut SynthContext) -> Block {
         let input = self.input.render(context);
-
-        let a0 = 1.0 - self.decay;
-        let b1 = self.decay;
+        let cutoff = self.cutoff.render(context);
 
         Block::from_sample_fn(|i| {
             let input = input[i];
 
+            let decay = lowpass_decay(cutoff[i], context.sample_rate());
+
+            let a0 = 1.0 - decay;
+            let b1 = decay;
+
             let output = input * a0 + self.buffer * b1;
             self.buffer = output;
             output
@@ -48,45 +71,830 @@ where
     }
 }
 
+impl<I, Cv> FrequencyResponse for SinglePoleLpf<I, Cv>
+where
+    Cv: Constant,
+{
+    fn response(&self, frequency: f32, sample_rate: u32) -> Complex {
+        let decay = lowpass_decay(self.cutoff.value(), sample_rate);
+        let z1 = analysis::delay(1.0, frequency, sample_rate);
+
+        Complex::new(1.0 - decay, 0.0) / (Complex::new(1.0, 0.0) - z1 * decay)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
-pub struct SinglePoleHpf<I> {
+pub struct SinglePoleHpf<I, Cv> {
     input: I,
-    decay: f32,
+    cutoff: Cv,
     buffer: f32,
 }
 
-impl<I> SinglePoleHpf<I>
+impl<I, Cv> SinglePoleHpf<I, Cv>
 where
     I: Operator,
+    Cv: Operator,
 {
-    pub fn hpf(input: I, cutoff: f32, sample_rate: u32) -> Self {
-        let fc = (cutoff / sample_rate as f32).min(0.5);
-        let decay = 0.0 - E.powf(-2.0 * PI * (0.5 - fc));
-
+    pub fn hpf(input: I, cutoff: Cv) -> Self {
         Self {
             input,
-            decay,
+            cutoff,
             buffer: 0.0,
         }
     }
 }
 
-impl<I> Operator for SinglePoleHpf<I>
+impl<I, Cv> Operator for SinglePoleHpf<I, Cv>
 where
     I: Operator,
+    Cv: Operator,
 {
     fn render(&mut self, context: &mut SynthContext) -> Block {
         let input = self.input.render(context);
-
-        let a0 = 1.0 + self.decay;
-        let b1 = self.decay;
+        let cutoff = self.cutoff.render(context);
 
         Block::from_sample_fn(|i| {
             let input = input[i];
 
+            let decay = highpass_decay(cutoff[i], context.sample_rate());
+
+            let a0 = 1.0 + decay;
+            let b1 = decay;
+
             let output = input * a0 + self.buffer * b1;
             self.buffer = output;
             output
         })
     }
 }
+
+impl<I, Cv> FrequencyResponse for SinglePoleHpf<I, Cv>
+where
+    Cv: Constant,
+{
+    fn response(&self, frequency: f32, sample_rate: u32) -> Complex {
+        let decay = highpass_decay(self.cutoff.value(), sample_rate);
+        let z1 = analysis::delay(1.0, frequency, sample_rate);
+
+        Complex::new(1.0 + decay, 0.0) / (Complex::new(1.0, 0.0) - z1 * decay)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiquadCoefficients {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+}
+
+impl BiquadCoefficients {
+    fn normalised(b0: f32, b1: f32, b2: f32, a0: f32, a1: f32, a2: f32) -> Self {
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+        }
+    }
+
+    fn omega(frequency: f32, sample_rate: u32) -> (f32, f32) {
+        let w0 = 2.0 * PI * normalised_cutoff(frequency, sample_rate).min(0.499);
+        (w0.cos(), w0.sin())
+    }
+
+    pub fn lowpass(frequency: f32, q: f32, sample_rate: u32) -> Self {
+        let (cos, sin) = Self::omega(frequency, sample_rate);
+        let alpha = sin / (2.0 * q);
+
+        Self::normalised(
+            (1.0 - cos) / 2.0,
+            1.0 - cos,
+            (1.0 - cos) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    pub fn highpass(frequency: f32, q: f32, sample_rate: u32) -> Self {
+        let (cos, sin) = Self::omega(frequency, sample_rate);
+        let alpha = sin / (2.0 * q);
+
+        Self::normalised(
+            (1.0 + cos) / 2.0,
+            -(1.0 + cos),
+            (1.0 + cos) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    pub fn allpass(frequency: f32, q: f32, sample_rate: u32) -> Self {
+        let (cos, sin) = Self::omega(frequency, sample_rate);
+        let alpha = sin / (2.0 * q);
+
+        Self::normalised(
+            1.0 - alpha,
+            -2.0 * cos,
+            1.0 + alpha,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    pub fn bandpass(frequency: f32, q: f32, sample_rate: u32) -> Self {
+        let (cos, sin) = Self::omega(frequency, sample_rate);
+        let alpha = sin / (2.0 * q);
+
+        Self::normalised(alpha, 0.0, -alpha, 1.0 + alpha, -2.0 * cos, 1.0 - alpha)
+    }
+}
+
+impl Default for BiquadCoefficients {
+    fn default() -> Self {
+        Self {
+            b0: 1.0,
+            b1: 0.0,
+            b2: 0.0,
+            a1: 0.0,
+            a2: 0.0,
+        }
+    }
+}
+
+impl FrequencyResponse for BiquadCoefficients {
+    fn response(&self, frequency: f32, sample_rate: u32) -> Complex {
+        let z1 = analysis::delay(1.0, frequency, sample_rate);
+        let z2 = analysis::delay(2.0, frequency, sample_rate);
+
+        let numerator = Complex::new(self.b0, 0.0) + z1 * self.b1 + z2 * self.b2;
+        let denominator = Complex::new(1.0, 0.0) + z1 * self.a1 + z2 * self.a2;
+
+        numerator / denominator
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Biquad {
+    pub(crate) coefficients: BiquadCoefficients,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl Biquad {
+    pub(crate) fn process(&mut self, input: f32) -> f32 {
+        let BiquadCoefficients { b0, b1, b2, a1, a2 } = self.coefficients;
+        let output = b0 * input + b1 * self.x1 + b2 * self.x2 - a1 * self.y1 - a2 * self.y2;
+
+        self.x2 = self.x1;
+        self.x1 = input;
+        self.y2 = self.y1;
+        self.y1 = output;
+
+        output
+    }
+}
+
+fn delay_samples(time: f32, sample_rate: u32) -> f32 {
+    time.max(0.0) * sample_rate as f32
+}
+
+fn feedback_gain(gain: f32) -> f32 {
+    gain.clamp(-0.999, 0.999)
+}
+
+#[derive(Debug, Clone)]
+pub struct FeedforwardComb<I, T, G> {
+    input: I,
+    time: T,
+    gain: G,
+    max_time: f32,
+    line: DelayLine,
+}
+
+impl<I, T, G> FeedforwardComb<I, T, G>
+where
+    I: Operator,
+    T: Operator,
+    G: Operator,
+{
+    pub fn new(input: I, time: T, gain: G, max_time: f32) -> Self {
+        Self {
+            input,
+            time,
+            gain,
+            max_time,
+            line: DelayLine::new(),
+        }
+    }
+}
+
+impl<I, T, G> Operator for FeedforwardComb<I, T, G>
+where
+    I: Operator,
+    T: Operator,
+    G: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let time = self.time.render(context);
+        let gain = self.gain.render(context);
+
+        self.line.allocate(self.max_time, context.sample_rate());
+
+        Block::from_sample_fn(|i| {
+            let delay = delay_samples(time[i], context.sample_rate());
+            let output = input[i] + gain[i] * self.line.read(delay);
+
+            self.line.write(input[i]);
+            output
+        })
+    }
+}
+
+impl<I, T, G> FrequencyResponse for FeedforwardComb<I, T, G>
+where
+    T: Constant,
+    G: Constant,
+{
+    fn response(&self, frequency: f32, sample_rate: u32) -> Complex {
+        let delay = delay_samples(self.time.value(), sample_rate);
+        let delayed = analysis::delay(delay, frequency, sample_rate);
+
+        Complex::new(1.0, 0.0) + delayed * self.gain.value()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FeedbackComb<I, T, G> {
+    input: I,
+    time: T,
+    gain: G,
+    max_time: f32,
+    line: DelayLine,
+}
+
+impl<I, T, G> FeedbackComb<I, T, G>
+where
+    I: Operator,
+    T: Operator,
+    G: Operator,
+{
+    pub fn new(input: I, time: T, gain: G, max_time: f32) -> Self {
+        Self {
+            input,
+            time,
+            gain,
+            max_time,
+            line: DelayLine::new(),
+        }
+    }
+}
+
+impl<I, T, G> Operator for FeedbackComb<I, T, G>
+where
+    I: Operator,
+    T: Operator,
+    G: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let time = self.time.render(context);
+        let gain = self.gain.render(context);
+
+        self.line.allocate(self.max_time, context.sample_rate());
+
+        Block::from_sample_fn(|i| {
+            let delay = delay_samples(time[i], context.sample_rate());
+            let output = input[i] + feedback_gain(gain[i]) * self.line.read(delay);
+
+            self.line.write(output);
+            output
+        })
+    }
+}
+
+impl<I, T, G> FrequencyResponse for FeedbackComb<I, T, G>
+where
+    T: Constant,
+    G: Constant,
+{
+    fn response(&self, frequency: f32, sample_rate: u32) -> Complex {
+        let delay = delay_samples(self.time.value(), sample_rate);
+        let delayed = analysis::delay(delay, frequency, sample_rate);
+
+        Complex::new(1.0, 0.0)
+            / (Complex::new(1.0, 0.0) - delayed * feedback_gain(self.gain.value()))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Allpass<I, T, G> {
+    input: I,
+    time: T,
+    gain: G,
+    max_time: f32,
+    line: DelayLine,
+}
+
+impl<I, T, G> Allpass<I, T, G>
+where
+    I: Operator,
+    T: Operator,
+    G: Operator,
+{
+    pub fn new(input: I, time: T, gain: G, max_time: f32) -> Self {
+        Self {
+            input,
+            time,
+            gain,
+            max_time,
+            line: DelayLine::new(),
+        }
+    }
+}
+
+impl<I, T, G> Operator for Allpass<I, T, G>
+where
+    I: Operator,
+    T: Operator,
+    G: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let time = self.time.render(context);
+        let gain = self.gain.render(context);
+
+        self.line.allocate(self.max_time, context.sample_rate());
+
+        Block::from_sample_fn(|i| {
+            let delay = delay_samples(time[i], context.sample_rate());
+            let gain = feedback_gain(gain[i]);
+
+            let delayed = self.line.read(delay);
+            let feedback = input[i] + gain * delayed;
+
+            self.line.write(feedback);
+            delayed - gain * feedback
+        })
+    }
+}
+
+impl<I, T, G> FrequencyResponse for Allpass<I, T, G>
+where
+    T: Constant,
+    G: Constant,
+{
+    fn response(&self, frequency: f32, sample_rate: u32) -> Complex {
+        let delay = delay_samples(self.time.value(), sample_rate);
+        let delayed = analysis::delay(delay, frequency, sample_rate);
+        let gain = feedback_gain(self.gain.value());
+
+        (delayed - Complex::new(gain, 0.0)) / (Complex::new(1.0, 0.0) - delayed * gain)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Formant {
+    frequency: f32,
+    gain: f32,
+    bandwidth: f32,
+}
+
+impl Formant {
+    fn coefficients(&self, sample_rate: u32) -> BiquadCoefficients {
+        let q = self.frequency / self.bandwidth;
+        BiquadCoefficients::bandpass(self.frequency, q, sample_rate)
+    }
+}
+
+const fn formant(frequency: f32, gain: f32, bandwidth: f32) -> Formant {
+    Formant {
+        frequency,
+        gain,
+        bandwidth,
+    }
+}
+
+const VOWELS: [[Formant; 3]; 5] = [
+    // a
+    [
+        formant(600.0, 1.0, 60.0),
+        formant(1040.0, 0.447, 70.0),
+        formant(2250.0, 0.355, 110.0),
+    ],
+    // e
+    [
+        formant(400.0, 1.0, 40.0),
+        formant(1620.0, 0.251, 80.0),
+        formant(2400.0, 0.355, 100.0),
+    ],
+    // i
+    [
+        formant(250.0, 1.0, 60.0),
+        formant(1750.0, 0.032, 90.0),
+        formant(2600.0, 0.158, 100.0),
+    ],
+    // o
+    [
+        formant(400.0, 1.0, 40.0),
+        formant(750.0, 0.282, 80.0),
+        formant(2400.0, 0.089, 100.0),
+    ],
+    // u
+    [
+        formant(350.0, 1.0, 40.0),
+        formant(600.0, 0.1, 80.0),
+        formant(2400.0, 0.025, 100.0),
+    ],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vowel {
+    A,
+    E,
+    I,
+    O,
+    U,
+}
+
+impl From<Vowel> for f32 {
+    fn from(vowel: Vowel) -> Self {
+        vowel as usize as f32
+    }
+}
+
+impl Constant for Vowel {
+    fn value(&self) -> f32 {
+        (*self).into()
+    }
+}
+
+impl Operator for Vowel {
+    fn render(&mut self, _: &mut SynthContext) -> Block {
+        Block([(*self).into(); BLOCK_SIZE])
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FormantFilter<I, Cv> {
+    input: I,
+    vowel: Cv,
+    bands: [Biquad; 3],
+}
+
+impl<I, Cv> FormantFilter<I, Cv>
+where
+    I: Operator,
+    Cv: Operator,
+{
+    pub fn new(input: I, vowel: Cv) -> Self {
+        Self {
+            input,
+            vowel,
+            bands: Default::default(),
+        }
+    }
+}
+
+fn morph_formants(vowel: f32) -> [Formant; 3] {
+    let vowel = vowel.clamp(0.0, (VOWELS.len() - 1) as f32);
+    let index = (vowel.floor() as usize).min(VOWELS.len() - 2);
+    let factor = vowel - index as f32;
+
+    let from = VOWELS[index];
+    let to = VOWELS[index + 1];
+
+    [0, 1, 2].map(|band| Formant {
+        frequency: from[band].frequency.lerp(to[band].frequency, factor),
+        gain: from[band].gain.lerp(to[band].gain, factor),
+        bandwidth: from[band].bandwidth.lerp(to[band].bandwidth, factor),
+    })
+}
+
+impl<I, Cv> Operator for FormantFilter<I, Cv>
+where
+    I: Operator,
+    Cv: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let vowel = self.vowel.render(context);
+
+        Block::from_sample_fn(|i| {
+            let formants = morph_formants(vowel[i]);
+
+            self.bands
+                .iter_mut()
+                .zip(formants)
+                .map(|(band, formant)| {
+                    band.coefficients = formant.coefficients(context.sample_rate());
+                    band.process(input[i]) * formant.gain
+                })
+                .sum()
+        })
+    }
+}
+
+impl<I, Cv> FrequencyResponse for FormantFilter<I, Cv>
+where
+    Cv: Constant,
+{
+    fn response(&self, frequency: f32, sample_rate: u32) -> Complex {
+        morph_formants(self.vowel.value())
+            .iter()
+            .fold(Complex::default(), |sum, formant| {
+                sum + formant
+                    .coefficients(sample_rate)
+                    .response(frequency, sample_rate)
+                    * formant.gain
+            })
+    }
+}
+
+const DC_BLOCKER_CUTOFF: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy)]
+pub struct DcBlocker<I> {
+    input: I,
+    previous_input: f32,
+    previous_output: f32,
+}
+
+impl<I> DcBlocker<I>
+where
+    I: Operator,
+{
+    pub fn new(input: I) -> Self {
+        Self {
+            input,
+            previous_input: 0.0,
+            previous_output: 0.0,
+        }
+    }
+}
+
+impl<I> Operator for DcBlocker<I>
+where
+    I: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+
+        let pole = lowpass_decay(DC_BLOCKER_CUTOFF, context.sample_rate());
+
+        Block::from_sample_fn(|i| {
+            let output = input[i] - self.previous_input + pole * self.previous_output;
+
+            self.previous_input = input[i];
+            self.previous_output = output;
+            output
+        })
+    }
+}
+
+impl<I> FrequencyResponse for DcBlocker<I> {
+    fn response(&self, frequency: f32, sample_rate: u32) -> Complex {
+        let pole = lowpass_decay(DC_BLOCKER_CUTOFF, sample_rate);
+        let z1 = analysis::delay(1.0, frequency, sample_rate);
+
+        (Complex::new(1.0, 0.0) - z1) / (Complex::new(1.0, 0.0) - z1 * pole)
+    }
+}
+
+const BUTTERWORTH_Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+// A Linkwitz-Riley crossover point, built from two cascaded Butterworth sections on
+// each side so that the low and high outputs sum to an allpass response.
+#[derive(Debug, Clone)]
+struct CrossoverPoint {
+    frequency: f32,
+    lowpass: [Biquad; 2],
+    highpass: [Biquad; 2],
+}
+
+impl CrossoverPoint {
+    fn update(&mut self, sample_rate: u32) {
+        let lowpass = BiquadCoefficients::lowpass(self.frequency, BUTTERWORTH_Q, sample_rate);
+        let highpass = BiquadCoefficients::highpass(self.frequency, BUTTERWORTH_Q, sample_rate);
+
+        for filter in self.lowpass.iter_mut() {
+            filter.coefficients = lowpass;
+        }
+
+        for filter in self.highpass.iter_mut() {
+            filter.coefficients = highpass;
+        }
+    }
+}
+
+fn cascade(filters: &mut [Biquad], input: f32) -> f32 {
+    filters
+        .iter_mut()
+        .fold(input, |sample, filter| filter.process(sample))
+}
+
+#[derive(Debug, Clone)]
+pub struct Crossover<I, const N: usize> {
+    input: I,
+    points: Vec<CrossoverPoint>,
+    // Every band below the last crossover point is phase-compensated by an allpass at
+    // each higher point, so that all bands line up when summed.
+    compensation: Vec<Vec<Biquad>>,
+}
+
+impl<I, const N: usize> Crossover<I, N>
+where
+    I: Operator,
+{
+    pub fn new(input: I, frequencies: impl IntoIterator<Item = f32>) -> Self {
+        assert!((2..=4).contains(&N), "crossover must have 2 to 4 bands");
+
+        let mut frequencies: Vec<_> = frequencies.into_iter().collect();
+        frequencies.sort_by(f32::total_cmp);
+
+        assert_eq!(
+            frequencies.len(),
+            N - 1,
+            "crossover with {N} bands needs {} frequencies",
+            N - 1
+        );
+
+        let points = frequencies
+            .into_iter()
+            .map(|frequency| CrossoverPoint {
+                frequency,
+                lowpass: Default::default(),
+                highpass: Default::default(),
+            })
+            .collect();
+
+        let compensation = (0..N - 1)
+            .map(|band| vec![Biquad::default(); N - 2 - band])
+            .collect();
+
+        Self {
+            input,
+            points,
+            compensation,
+        }
+    }
+}
+
+impl<I, const N: usize> Crossover<I, N> {
+    pub fn band_response(&self, band: usize, frequency: f32, sample_rate: u32) -> Complex {
+        assert!(band < N, "crossover band out of range");
+
+        let response =
+            |coefficients: BiquadCoefficients| coefficients.response(frequency, sample_rate);
+        let squared = |coefficients| response(coefficients) * response(coefficients);
+
+        let highs =
+            self.points[..band.min(N - 1)]
+                .iter()
+                .fold(Complex::new(1.0, 0.0), |sum, point| {
+                    sum * squared(BiquadCoefficients::highpass(
+                        point.frequency,
+                        BUTTERWORTH_Q,
+                        sample_rate,
+                    ))
+                });
+
+        if band == N - 1 {
+            return highs;
+        }
+
+        let point = &self.points[band];
+        let low = squared(BiquadCoefficients::lowpass(
+            point.frequency,
+            BUTTERWORTH_Q,
+            sample_rate,
+        ));
+
+        self.points[band + 1..]
+            .iter()
+            .fold(highs * low, |sum, point| {
+                sum * response(BiquadCoefficients::allpass(
+                    point.frequency,
+                    BUTTERWORTH_Q,
+                    sample_rate,
+                ))
+            })
+    }
+}
+
+impl<I, const N: usize> MultiOperator<N> for Crossover<I, N>
+where
+    I: Operator,
+{
+    fn render_outputs(&mut self, context: &mut SynthContext) -> [Block; N] {
+        let input = self.input.render(context);
+        let sample_rate = context.sample_rate();
+
+        for point in self.points.iter_mut() {
+            point.update(sample_rate);
+        }
+
+        for (band, allpasses) in self.compensation.iter_mut().enumerate() {
+            for (allpass, point) in allpasses.iter_mut().zip(&self.points[band + 1..]) {
+                allpass.coefficients =
+                    BiquadCoefficients::allpass(point.frequency, BUTTERWORTH_Q, sample_rate);
+            }
+        }
+
+        let mut outputs = [Block::silence(); N];
+
+        for i in 0..BLOCK_SIZE {
+            let mut rest = input[i];
+
+            for (band, point) in self.points.iter_mut().enumerate() {
+                let low = cascade(&mut point.lowpass, rest);
+                rest = cascade(&mut point.highpass, rest);
+
+                outputs[band][i] = cascade(&mut self.compensation[band], low);
+            }
+
+            outputs[N - 1][i] = rest;
+        }
+
+        outputs
+    }
+}
+
+impl<I, const N: usize> FrequencyResponse for Split<Crossover<I, N>, N> {
+    fn response(&self, frequency: f32, sample_rate: u32) -> Complex {
+        self.operator()
+            .band_response(self.index(), frequency, sample_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::decibels;
+    use crate::analysis::measure_response;
+    use crate::OperatorExt;
+    use crate::Silence;
+
+    const SAMPLE_RATE: u32 = 48_000;
+
+    fn close(measured: f32, expected: f32) -> bool {
+        (decibels(measured) - decibels(expected)).abs() < 0.1
+    }
+
+    #[test]
+    fn lowpass_measures_minus_three_decibels_at_cutoff() {
+        let filter = SinglePoleLpf::lpf(Silence, 1000.0);
+        let spectrum = measure_response(SAMPLE_RATE, 8192, |impulse| impulse.simple_lpf(1000.0));
+
+        for frequency in [100.0, 1000.0, 5000.0] {
+            assert!(close(
+                spectrum.magnitude(frequency),
+                filter.magnitude(frequency, SAMPLE_RATE)
+            ));
+        }
+
+        let corner = (1..SAMPLE_RATE / 2)
+            .map(|frequency| frequency as f32)
+            .find(|&frequency| decibels(spectrum.magnitude(frequency)) < -3.0)
+            .unwrap();
+
+        assert!((corner - 1000.0).abs() < 50.0, "corner at {corner} Hz");
+    }
+
+    #[test]
+    fn response_is_known_before_rendering() {
+        let lowpass = SinglePoleLpf::lpf(Silence, 100.0);
+        let formant = FormantFilter::new(Silence, Vowel::A);
+
+        assert!(lowpass.magnitude(10_000.0, SAMPLE_RATE) < 0.1);
+        assert!(formant.magnitude(600.0, SAMPLE_RATE) > 0.5);
+    }
+
+    #[test]
+    fn crossover_bands_match_measurement() {
+        let bands = Silence.crossover::<3>([200.0, 2000.0]);
+
+        for (band, analytic) in bands.iter().enumerate() {
+            let spectrum = measure_response(SAMPLE_RATE, 8192, |impulse| {
+                impulse.crossover::<3>([200.0, 2000.0])[band].clone()
+            });
+
+            // Deep in the stopband the measurement only sees rounding noise.
+            for frequency in [50.0, 200.0, 700.0, 2000.0, 8000.0] {
+                let expected = analytic.magnitude(frequency, SAMPLE_RATE);
+
+                assert!(
+                    decibels(expected) < -60.0 || close(spectrum.magnitude(frequency), expected)
+                );
+            }
+        }
+    }
+}
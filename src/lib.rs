@@ -1,16 +1,36 @@
+pub mod analysis;
 pub mod branch;
 pub mod comparators;
 pub mod delay;
 pub mod detect;
+pub mod distortion;
+pub mod dynamics;
 pub mod envelope;
 pub mod filter;
+pub mod looper;
 pub mod math;
+pub mod modulation;
+pub mod oversample;
+pub mod pitch;
+pub mod reverb;
+pub mod rhythm;
 pub mod scales;
+pub mod sequencer;
 pub mod sinks;
+pub mod slew;
 pub mod sources;
 
+pub use branch::FeedbackReturn;
+pub use branch::MultiOperator;
+use filter::Allpass;
+use filter::Crossover;
+use filter::DcBlocker;
+use filter::FeedbackComb;
+use filter::FeedforwardComb;
+use filter::FormantFilter;
 use filter::SinglePoleHpf;
 use filter::SinglePoleLpf;
+pub use sequencer::Step;
 pub use sinks::AudioOut;
 pub use sinks::CpalMono;
 pub use sinks::Sink;
@@ -18,6 +38,7 @@ pub use sinks::WavFile;
 pub use sources::Clock;
 pub use sources::Const;
 pub use sources::Gate;
+pub use sources::Impulse;
 pub use sources::Saw;
 pub use sources::Silence;
 pub use sources::Sine;
@@ -29,9 +50,19 @@ use branch::*;
 use comparators::*;
 use delay::*;
 use detect::*;
+use distortion::*;
+use dynamics::*;
 use envelope::*;
+use looper::*;
 use math::*;
+use modulation::*;
+use oversample::*;
+use pitch::*;
+use reverb::*;
+use rhythm::*;
 use scales::*;
+use sequencer::*;
+use slew::*;
 
 use std::fmt::Debug;
 use std::ops::Deref;
@@ -162,6 +193,33 @@ where
         Clip { input: self, level }
     }
 
+    fn saturate<Cv>(self, drive: Cv, curve: Saturation) -> Saturate<Self, Cv> {
+        Saturate {
+            input: self,
+            drive,
+            curve,
+        }
+    }
+
+    fn fold<Cv>(self, amount: Cv) -> Fold<Self, Cv> {
+        Fold {
+            input: self,
+            amount,
+        }
+    }
+
+    fn crush<Cv>(self, bits: Cv) -> Bitcrush<Self, Cv> {
+        Bitcrush { input: self, bits }
+    }
+
+    fn decimate<Cv>(self, rate: Cv) -> Decimate<Self, Cv>
+    where
+        Cv: Operator,
+        Self: Operator,
+    {
+        Decimate::new(self, rate)
+    }
+
     fn mix<Rhs, Cv>(self, rhs: Rhs, level: Cv) -> Mix<Self, Rhs, Cv>
     where
         Rhs: Operator,
@@ -198,6 +256,15 @@ where
         }
     }
 
+    fn follow<A, R>(self, attack: A, release: R) -> Follower<Self, A, R>
+    where
+        A: Operator,
+        R: Operator,
+        Self: Operator,
+    {
+        Follower::new(self, attack, release)
+    }
+
     fn sequential_switch(
         self,
         signals: impl IntoIterator<Item = Box<dyn Operator>>,
@@ -208,6 +275,55 @@ where
         SequentialSwitch::new(self.trigger(), signals)
     }
 
+    fn divide<N>(self, division: N) -> Divider<Self, N, Silence>
+    where
+        N: Operator,
+        Self: Operator,
+    {
+        Divider::new(self, division)
+    }
+
+    fn multiply<N>(self, multiplication: N) -> Multiplier<Self, N, Silence>
+    where
+        N: Operator,
+        Self: Operator,
+    {
+        Multiplier::new(self, multiplication)
+    }
+
+    fn count<N>(self, length: N) -> Counter<Self, N, Silence>
+    where
+        N: Operator,
+        Self: Operator,
+    {
+        Counter::new(self, length)
+    }
+
+    fn euclidean<S, P, R>(
+        self,
+        steps: S,
+        pulses: P,
+        rotation: R,
+    ) -> Euclidean<Self, S, P, R, Silence>
+    where
+        S: Operator,
+        P: Operator,
+        R: Operator,
+        Self: Operator,
+    {
+        Euclidean::new(self, steps, pulses, rotation)
+    }
+
+    fn step_sequencer(
+        self,
+        steps: impl IntoIterator<Item = Step>,
+    ) -> StepSequencer<Self, Const, Silence>
+    where
+        Self: Operator,
+    {
+        StepSequencer::new(self, steps)
+    }
+
     fn ad_envelope<A, D>(self, attack: A, decay: D) -> Ad<A, D, Self>
     where
         A: Operator,
@@ -217,11 +333,184 @@ where
         envelope::ad(self, attack, decay)
     }
 
-    fn delay(self, time: f32, sample_rate: u32) -> Delay<Self>
+    fn adsr_envelope<A, D, S, R>(
+        self,
+        attack: A,
+        decay: D,
+        sustain: S,
+        release: R,
+    ) -> Adsr<A, D, S, R, Self>
+    where
+        A: Operator,
+        D: Operator,
+        S: Operator,
+        R: Operator,
+        Self: Operator,
+    {
+        envelope::adsr(self, attack, decay, sustain, release)
+    }
+
+    fn function_generator<R, F>(
+        self,
+        rise: R,
+        fall: F,
+    ) -> FunctionGenerator<Self, R, F, Const, Const>
+    where
+        R: Operator,
+        F: Operator,
+        Self: Operator,
+    {
+        envelope::function_generator(self, rise, fall)
+    }
+
+    fn mseg(self, breakpoints: impl IntoIterator<Item = Breakpoint>) -> Mseg<Self>
+    where
+        Self: Operator,
+    {
+        envelope::mseg(self, breakpoints)
+    }
+
+    fn delay<T>(self, time: T, max_time: f32) -> Delay<Self, T>
+    where
+        T: Operator,
+        Self: Operator,
+    {
+        Delay::new(self, time, max_time)
+    }
+
+    fn slew<R, F>(self, rise: R, fall: F) -> Slew<Self, R, F>
+    where
+        R: Operator,
+        F: Operator,
+        Self: Operator,
+    {
+        Slew::new(self, rise, fall)
+    }
+
+    fn compress<T, R, A, Rl>(
+        self,
+        threshold: T,
+        ratio: R,
+        attack: A,
+        release: Rl,
+    ) -> Compressor<Self, T, R, A, Rl, Const, Const, Silence>
+    where
+        T: Operator,
+        R: Operator,
+        A: Operator,
+        Rl: Operator,
+        Self: Operator,
+    {
+        Compressor::new(self, threshold, ratio, attack, release)
+    }
+
+    fn limit<C, R>(self, ceiling: C, release: R) -> Limiter<Self, C, R>
     where
+        C: Operator,
+        R: Operator,
         Self: Operator,
     {
-        Delay::delay(self, time, sample_rate)
+        Limiter::new(self, ceiling, release)
+    }
+
+    fn noise_gate<T, A, H, R>(
+        self,
+        threshold: T,
+        attack: A,
+        hold: H,
+        release: R,
+    ) -> NoiseGate<Self, T, A, H, R, Const, Const, Silence>
+    where
+        T: Operator,
+        A: Operator,
+        H: Operator,
+        R: Operator,
+        Self: Operator,
+    {
+        NoiseGate::new(self, threshold, attack, hold, release)
+    }
+
+    fn chorus<R, D, F, M>(self, rate: R, depth: D, feedback: F, mix: M) -> Chorus<Self, R, D, F, M>
+    where
+        R: Operator,
+        D: Operator,
+        F: Operator,
+        M: Operator,
+        Self: Operator,
+    {
+        Chorus::new(self, rate, depth, feedback, mix)
+    }
+
+    fn flanger<R, D, F, M>(
+        self,
+        rate: R,
+        depth: D,
+        feedback: F,
+        mix: M,
+    ) -> Flanger<Self, R, D, F, M>
+    where
+        R: Operator,
+        D: Operator,
+        F: Operator,
+        M: Operator,
+        Self: Operator,
+    {
+        Flanger::new(self, rate, depth, feedback, mix)
+    }
+
+    fn phaser<R, D, F, M>(self, rate: R, depth: D, feedback: F, mix: M) -> Phaser<Self, R, D, F, M>
+    where
+        R: Operator,
+        D: Operator,
+        F: Operator,
+        M: Operator,
+        Self: Operator,
+    {
+        Phaser::new(self, rate, depth, feedback, mix)
+    }
+
+    fn oversample<F, O>(self, factor: usize, build: F) -> Oversample<Self, O>
+    where
+        F: FnOnce(OversampledInput) -> O,
+        O: Operator,
+        Self: Operator,
+    {
+        Oversample::new(self, factor, build)
+    }
+
+    fn pitch_shift<Cv>(self, shift: Cv) -> PitchShift<Self, Cv>
+    where
+        Cv: Operator,
+        Self: Operator,
+    {
+        PitchShift::new(self, shift)
+    }
+
+    fn reverb<S, D, Dp, P, M>(
+        self,
+        size: S,
+        decay: D,
+        damping: Dp,
+        pre_delay: P,
+        mix: M,
+    ) -> Reverb<Self, S, D, Dp, P, M>
+    where
+        S: Operator,
+        D: Operator,
+        Dp: Operator,
+        P: Operator,
+        M: Operator,
+        Self: Operator,
+    {
+        Reverb::new(self, size, decay, damping, pre_delay, mix)
+    }
+
+    fn looper<R>(self, record: R, max_time: f32) -> Looper<Self, R, Const, Const, Silence>
+    where
+        R: Operator,
+        Self: Operator,
+    {
+        Looper::new(self, record, max_time)
     }
 
     fn tap(self) -> Tap<Self>
@@ -231,6 +520,13 @@ where
         Tap::tap(self)
     }
 
+    fn feedback_send(self, feedback: &FeedbackReturn) -> FeedbackSend<Self>
+    where
+        Self: Operator,
+    {
+        FeedbackSend::new(self, feedback)
+    }
+
     fn quantize(self, mode: QuantizeMode) -> Quantizer<Self> {
         Quantizer { input: self, mode }
     }
@@ -259,18 +555,72 @@ where
         NotEqualTo { lhs: self, rhs }
     }
 
-    fn simple_lpf(self, cutoff: f32, sample_rate: u32) -> SinglePoleLpf<Self>
+    fn simple_lpf<Cv>(self, cutoff: Cv) -> SinglePoleLpf<Self, Cv>
+    where
+        Cv: Operator,
+        Self: Operator,
+    {
+        SinglePoleLpf::lpf(self, cutoff)
+    }
+
+    fn simple_hpf<Cv>(self, cutoff: Cv) -> SinglePoleHpf<Self, Cv>
+    where
+        Cv: Operator,
+        Self: Operator,
+    {
+        SinglePoleHpf::hpf(self, cutoff)
+    }
+
+    fn comb_feedforward<T, G>(self, time: T, gain: G, max_time: f32) -> FeedforwardComb<Self, T, G>
+    where
+        T: Operator,
+        G: Operator,
+        Self: Operator,
+    {
+        FeedforwardComb::new(self, time, gain, max_time)
+    }
+
+    fn comb_feedback<T, G>(self, time: T, gain: G, max_time: f32) -> FeedbackComb<Self, T, G>
+    where
+        T: Operator,
+        G: Operator,
+        Self: Operator,
+    {
+        FeedbackComb::new(self, time, gain, max_time)
+    }
+
+    fn allpass<T, G>(self, time: T, gain: G, max_time: f32) -> Allpass<Self, T, G>
+    where
+        T: Operator,
+        G: Operator,
+        Self: Operator,
+    {
+        Allpass::new(self, time, gain, max_time)
+    }
+
+    fn dc_block(self) -> DcBlocker<Self>
+    where
+        Self: Operator,
+    {
+        DcBlocker::new(self)
+    }
+
+    fn crossover<const N: usize>(
+        self,
+        frequencies: impl IntoIterator<Item = f32>,
+    ) -> [Split<Crossover<Self, N>, N>; N]
     where
         Self: Operator,
     {
-        SinglePoleLpf::lpf(self, cutoff, sample_rate)
+        Crossover::new(self, frequencies).split()
     }
 
-    fn simple_hpf(self, cutoff: f32, sample_rate: u32) -> SinglePoleHpf<Self>
+    fn formant<Cv>(self, vowel: Cv) -> FormantFilter<Self, Cv>
     where
+        Cv: Operator,
         Self: Operator,
     {
-        SinglePoleHpf::hpf(self, cutoff, sample_rate)
+        FormantFilter::new(self, vowel)
     }
 }
 
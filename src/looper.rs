@@ -0,0 +1,240 @@
+use crate::detect::Trigger;
+use crate::detect::TriggerState;
+use crate::sources::Const;
+use crate::sources::Silence;
+use crate::Block;
+use crate::Operator;
+use crate::OperatorExt;
+use crate::SynthContext;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LooperState {
+    Empty,
+    Armed,
+    Recording,
+    Closing,
+    Playing,
+    Overdubbing,
+}
+
+#[derive(Debug, Clone)]
+pub struct Looper<I, R, S, Rv, C> {
+    input: I,
+    record: R,
+    speed: S,
+    reverse: Rv,
+    clock: Trigger<C>,
+    synced: bool,
+    max_time: f32,
+    state: LooperState,
+    buffer: Vec<f32>,
+    length: usize,
+    position: f32,
+}
+
+impl<I, R> Looper<I, R, Const, Const, Silence>
+where
+    I: Operator,
+    R: Operator,
+{
+    pub fn new(input: I, record: R, max_time: f32) -> Self {
+        Self {
+            input,
+            record,
+            speed: Const(1.0),
+            reverse: Const(0.0),
+            clock: Silence.trigger(),
+            synced: false,
+            max_time,
+            state: LooperState::Empty,
+            buffer: Vec::new(),
+            length: 0,
+            position: 0.0,
+        }
+    }
+}
+
+impl<I, R, S, Rv, C> Looper<I, R, S, Rv, C> {
+    pub fn speed<Cv>(self, speed: Cv) -> Looper<I, R, Cv, Rv, C>
+    where
+        Cv: Operator,
+    {
+        Looper {
+            input: self.input,
+            record: self.record,
+            speed,
+            reverse: self.reverse,
+            clock: self.clock,
+            synced: self.synced,
+            max_time: self.max_time,
+            state: self.state,
+            buffer: self.buffer,
+            length: self.length,
+            position: self.position,
+        }
+    }
+
+    pub fn reverse<G>(self, reverse: G) -> Looper<I, R, S, G, C>
+    where
+        G: Operator,
+    {
+        Looper {
+            input: self.input,
+            record: self.record,
+            speed: self.speed,
+            reverse,
+            clock: self.clock,
+            synced: self.synced,
+            max_time: self.max_time,
+            state: self.state,
+            buffer: self.buffer,
+            length: self.length,
+            position: self.position,
+        }
+    }
+
+    // Starts and stops the first recording on clock triggers, so that the loop length is
+    // always a whole number of clock periods.
+    pub fn sync<T>(self, clock: T) -> Looper<I, R, S, Rv, T>
+    where
+        T: Operator,
+    {
+        Looper {
+            input: self.input,
+            record: self.record,
+            speed: self.speed,
+            reverse: self.reverse,
+            clock: clock.trigger(),
+            synced: true,
+            max_time: self.max_time,
+            state: self.state,
+            buffer: self.buffer,
+            length: self.length,
+            position: self.position,
+        }
+    }
+
+    fn finish_recording(&mut self) {
+        self.state = if self.length > 0 {
+            LooperState::Playing
+        } else {
+            LooperState::Empty
+        };
+        self.position = 0.0;
+    }
+
+    fn read(&self) -> f32 {
+        let whole = self.position.floor();
+        let fract = self.position - whole;
+
+        let a = self.buffer[whole as usize % self.length];
+        let b = self.buffer[(whole as usize + 1) % self.length];
+
+        a + (b - a) * fract
+    }
+}
+
+impl<I, R, S, Rv, C> Operator for Looper<I, R, S, Rv, C>
+where
+    I: Operator,
+    R: Operator,
+    S: Operator,
+    Rv: Operator,
+    C: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let record = self.record.render(context);
+        let speed = self.speed.render(context);
+        let reverse = self.reverse.render(context);
+        let clock = self.clock.render(context);
+
+        let capacity = (self.max_time.max(0.0) * context.sample_rate() as f32).ceil() as usize;
+
+        if self.buffer.len() != capacity {
+            self.buffer = vec![0.0; capacity];
+            self.length = 0;
+            self.state = LooperState::Empty;
+        }
+
+        Block::from_sample_fn(|i| {
+            let held = TriggerState::from(record[i]) == TriggerState::High;
+            let tick = TriggerState::from(clock[i]) == TriggerState::High;
+
+            self.state = match self.state {
+                // A press landing on a tick starts straight away rather than waiting a period.
+                LooperState::Empty | LooperState::Armed if held && (tick || !self.synced) => {
+                    self.length = 0;
+                    LooperState::Recording
+                }
+                LooperState::Empty if held => LooperState::Armed,
+                LooperState::Armed if !held => LooperState::Empty,
+                LooperState::Recording if !held && self.synced => LooperState::Closing,
+                LooperState::Recording if !held => {
+                    self.finish_recording();
+                    self.state
+                }
+                LooperState::Closing if tick => {
+                    self.finish_recording();
+                    self.state
+                }
+                LooperState::Playing if held => LooperState::Overdubbing,
+                LooperState::Overdubbing if !held => LooperState::Playing,
+                state => state,
+            };
+
+            match self.state {
+                LooperState::Empty | LooperState::Armed => 0.0,
+                LooperState::Recording | LooperState::Closing => {
+                    if self.length < self.buffer.len() {
+                        self.buffer[self.length] = input[i];
+                        self.length += 1;
+                    } else {
+                        self.finish_recording();
+                    }
+
+                    0.0
+                }
+                LooperState::Playing | LooperState::Overdubbing => {
+                    let sample = self.read();
+
+                    if self.state == LooperState::Overdubbing {
+                        let index = self.position as usize % self.length;
+                        self.buffer[index] += input[i];
+                    }
+
+                    let direction = if TriggerState::from(reverse[i]) == TriggerState::High {
+                        -1.0
+                    } else {
+                        1.0
+                    };
+
+                    self.position =
+                        (self.position + speed[i] * direction).rem_euclid(self.length as f32);
+
+                    sample
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::impulse_response;
+    use crate::Clock;
+    use crate::Gate;
+    use crate::OperatorExt;
+    use crate::Sine;
+
+    #[test]
+    fn synced_press_on_a_tick_records() {
+        let output = impulse_response(8_000, 32_000, |_| {
+            Sine::oscillator(440.0)
+                .looper(Gate::bpm(300.0), 1.0)
+                .sync(Clock::bpm(600.0))
+        });
+
+        assert!(output.iter().any(|&sample| sample != 0.0));
+    }
+}
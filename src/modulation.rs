@@ -0,0 +1,270 @@
+use std::f32::consts::PI;
+
+use crate::delay::DelayLine;
+use crate::delay::Interpolation;
+use crate::Block;
+use crate::Lerp;
+use crate::Operator;
+use crate::SynthContext;
+
+const MAX_FEEDBACK: f32 = 0.95;
+
+const CHORUS_DELAY: f32 = 0.02;
+const CHORUS_DEPTH: f32 = 0.008;
+
+const FLANGER_DELAY: f32 = 0.001;
+const FLANGER_DEPTH: f32 = 0.005;
+
+const PHASER_STAGES: usize = 6;
+const PHASER_MIN_FREQUENCY: f32 = 200.0;
+const PHASER_OCTAVES: f32 = 5.0;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Lfo {
+    phase: f32,
+}
+
+impl Lfo {
+    fn next(&mut self, rate: f32, sample_time: f32) -> f32 {
+        let sample = (self.phase * 2.0 * PI).sin();
+        self.phase = (self.phase + rate * sample_time).rem_euclid(1.0);
+        sample
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ModulatedDelay {
+    line: DelayLine,
+    lfo: Lfo,
+}
+
+impl ModulatedDelay {
+    fn new() -> Self {
+        Self {
+            line: DelayLine::new(),
+            lfo: Lfo::default(),
+        }
+    }
+
+    fn process(&mut self, input: f32, delay: f32, feedback: f32) -> f32 {
+        let feedback = feedback.clamp(-MAX_FEEDBACK, MAX_FEEDBACK);
+        let output = self.line.interpolate(delay, Interpolation::Cubic);
+
+        self.line.write(input + output * feedback);
+        output
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Chorus<I, R, D, F, M> {
+    input: I,
+    rate: R,
+    depth: D,
+    feedback: F,
+    mix: M,
+    delay: ModulatedDelay,
+}
+
+impl<I, R, D, F, M> Chorus<I, R, D, F, M>
+where
+    I: Operator,
+    R: Operator,
+    D: Operator,
+    F: Operator,
+    M: Operator,
+{
+    pub fn new(input: I, rate: R, depth: D, feedback: F, mix: M) -> Self {
+        Self {
+            input,
+            rate,
+            depth,
+            feedback,
+            mix,
+            delay: ModulatedDelay::new(),
+        }
+    }
+}
+
+impl<I, R, D, F, M> Operator for Chorus<I, R, D, F, M>
+where
+    I: Operator,
+    R: Operator,
+    D: Operator,
+    F: Operator,
+    M: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let rate = self.rate.render(context);
+        let depth = self.depth.render(context);
+        let feedback = self.feedback.render(context);
+        let mix = self.mix.render(context);
+
+        let sample_rate = context.sample_rate() as f32;
+        self.delay
+            .line
+            .allocate(CHORUS_DELAY + CHORUS_DEPTH, context.sample_rate());
+
+        Block::from_sample_fn(|i| {
+            let lfo = self.delay.lfo.next(rate[i], context.sample_time());
+            let time = CHORUS_DELAY + lfo * depth[i].clamp(0.0, 1.0) * CHORUS_DEPTH;
+
+            let wet = self
+                .delay
+                .process(input[i], time * sample_rate, feedback[i]);
+            input[i].lerp(wet, mix[i])
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Flanger<I, R, D, F, M> {
+    input: I,
+    rate: R,
+    depth: D,
+    feedback: F,
+    mix: M,
+    delay: ModulatedDelay,
+}
+
+impl<I, R, D, F, M> Flanger<I, R, D, F, M>
+where
+    I: Operator,
+    R: Operator,
+    D: Operator,
+    F: Operator,
+    M: Operator,
+{
+    pub fn new(input: I, rate: R, depth: D, feedback: F, mix: M) -> Self {
+        Self {
+            input,
+            rate,
+            depth,
+            feedback,
+            mix,
+            delay: ModulatedDelay::new(),
+        }
+    }
+}
+
+impl<I, R, D, F, M> Operator for Flanger<I, R, D, F, M>
+where
+    I: Operator,
+    R: Operator,
+    D: Operator,
+    F: Operator,
+    M: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let rate = self.rate.render(context);
+        let depth = self.depth.render(context);
+        let feedback = self.feedback.render(context);
+        let mix = self.mix.render(context);
+
+        let sample_rate = context.sample_rate() as f32;
+        self.delay
+            .line
+            .allocate(FLANGER_DELAY + FLANGER_DEPTH, context.sample_rate());
+
+        Block::from_sample_fn(|i| {
+            let lfo = self.delay.lfo.next(rate[i], context.sample_time());
+            let sweep = (lfo + 1.0) * 0.5;
+            let time = FLANGER_DELAY + sweep * depth[i].clamp(0.0, 1.0) * FLANGER_DEPTH;
+
+            let wet = self
+                .delay
+                .process(input[i], time * sample_rate, feedback[i]);
+            input[i].lerp(wet, mix[i])
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct AllpassStage {
+    previous_input: f32,
+    previous_output: f32,
+}
+
+impl AllpassStage {
+    fn process(&mut self, input: f32, coefficient: f32) -> f32 {
+        let output = coefficient * input + self.previous_input - coefficient * self.previous_output;
+
+        self.previous_input = input;
+        self.previous_output = output;
+        output
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Phaser<I, R, D, F, M> {
+    input: I,
+    rate: R,
+    depth: D,
+    feedback: F,
+    mix: M,
+    lfo: Lfo,
+    stages: [AllpassStage; PHASER_STAGES],
+    previous: f32,
+}
+
+impl<I, R, D, F, M> Phaser<I, R, D, F, M>
+where
+    I: Operator,
+    R: Operator,
+    D: Operator,
+    F: Operator,
+    M: Operator,
+{
+    pub fn new(input: I, rate: R, depth: D, feedback: F, mix: M) -> Self {
+        Self {
+            input,
+            rate,
+            depth,
+            feedback,
+            mix,
+            lfo: Lfo::default(),
+            stages: Default::default(),
+            previous: 0.0,
+        }
+    }
+}
+
+impl<I, R, D, F, M> Operator for Phaser<I, R, D, F, M>
+where
+    I: Operator,
+    R: Operator,
+    D: Operator,
+    F: Operator,
+    M: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let rate = self.rate.render(context);
+        let depth = self.depth.render(context);
+        let feedback = self.feedback.render(context);
+        let mix = self.mix.render(context);
+
+        let nyquist = context.sample_rate() as f32 * 0.49;
+
+        Block::from_sample_fn(|i| {
+            let lfo = self.lfo.next(rate[i], context.sample_time());
+            let sweep = (lfo + 1.0) * 0.5 * depth[i].clamp(0.0, 1.0);
+
+            let frequency = crate::volt_octave(PHASER_MIN_FREQUENCY, sweep * PHASER_OCTAVES);
+            let tan = (PI * frequency.min(nyquist) * context.sample_time()).tan();
+            let coefficient = (tan - 1.0) / (tan + 1.0);
+
+            let feedback = feedback[i].clamp(-MAX_FEEDBACK, MAX_FEEDBACK);
+            let wet = self
+                .stages
+                .iter_mut()
+                .fold(input[i] + self.previous * feedback, |sample, stage| {
+                    stage.process(sample, coefficient)
+                });
+
+            self.previous = wet;
+            input[i].lerp(wet, mix[i])
+        })
+    }
+}
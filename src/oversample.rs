@@ -0,0 +1,142 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::filter::Biquad;
+use crate::filter::BiquadCoefficients;
+use crate::Block;
+use crate::Operator;
+use crate::SynthContext;
+use crate::BLOCK_SIZE;
+
+// Section Qs of an 8th-order Butterworth lowpass.
+const BUTTERWORTH_8_Q: [f32; 4] = [0.5098, 0.6013, 0.9000, 2.5629];
+
+// Keeps a little headroom below the original Nyquist frequency for the filter skirt.
+const CUTOFF: f32 = 0.45;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct AntiAliasingFilter {
+    sections: [Biquad; 4],
+}
+
+impl AntiAliasingFilter {
+    fn update(&mut self, cutoff: f32, sample_rate: u32) {
+        for (section, q) in self.sections.iter_mut().zip(BUTTERWORTH_8_Q) {
+            section.coefficients = BiquadCoefficients::lowpass(cutoff, q, sample_rate);
+        }
+    }
+
+    fn process(&mut self, input: f32) -> f32 {
+        self.sections
+            .iter_mut()
+            .fold(input, |sample, section| section.process(sample))
+    }
+}
+
+// Feeds the upsampled input into an oversampled subgraph, one block at a time. Every clone
+// reads the same samples, so the input can be used more than once in the subgraph.
+#[derive(Debug, Clone)]
+pub struct OversampledInput {
+    samples: Rc<RefCell<Vec<f32>>>,
+    factor: usize,
+}
+
+impl Operator for OversampledInput {
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let chunk = (context.sample_count as usize / BLOCK_SIZE) % self.factor;
+        let samples = self.samples.borrow();
+
+        Block::from_sample_fn(|i| samples[chunk * BLOCK_SIZE + i])
+    }
+}
+
+// Runs a subgraph at a multiple of the context sample rate. Operators inside the subgraph
+// render against their own context, so taps shared with the outer graph won't line up.
+pub struct Oversample<I, O> {
+    input: I,
+    inner: O,
+    factor: usize,
+    samples: Rc<RefCell<Vec<f32>>>,
+    context: SynthContext,
+    upsampler: AntiAliasingFilter,
+    downsampler: AntiAliasingFilter,
+}
+
+impl<I, O> Oversample<I, O>
+where
+    I: Operator,
+    O: Operator,
+{
+    pub fn new<F>(input: I, factor: usize, build: F) -> Self
+    where
+        F: FnOnce(OversampledInput) -> O,
+    {
+        assert!(factor >= 1, "oversampling factor must be at least 1");
+
+        let samples = Rc::new(RefCell::new(vec![0.0; BLOCK_SIZE * factor]));
+        let inner = build(OversampledInput {
+            samples: Rc::clone(&samples),
+            factor,
+        });
+
+        Self {
+            input,
+            inner,
+            factor,
+            samples,
+            context: SynthContext::new(0),
+            upsampler: AntiAliasingFilter::default(),
+            downsampler: AntiAliasingFilter::default(),
+        }
+    }
+}
+
+impl<I, O> Operator for Oversample<I, O>
+where
+    I: Operator,
+    O: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+
+        let sample_rate = context.sample_rate() * self.factor as u32;
+
+        if self.context.sample_rate() != sample_rate {
+            let cutoff = context.sample_rate() as f32 * CUTOFF;
+
+            self.context = SynthContext::new(sample_rate);
+            self.upsampler.update(cutoff, sample_rate);
+            self.downsampler.update(cutoff, sample_rate);
+        }
+
+        // Zero-stuff the input and filter out the images, scaling up to make up for the
+        // energy lost to the inserted zeros.
+        for (i, sample) in self.samples.borrow_mut().iter_mut().enumerate() {
+            let stuffed = if i.is_multiple_of(self.factor) {
+                input[i / self.factor] * self.factor as f32
+            } else {
+                0.0
+            };
+
+            *sample = self.upsampler.process(stuffed);
+        }
+
+        let mut output = Block::silence();
+
+        for chunk in 0..self.factor {
+            let block = self.inner.render(&mut self.context);
+            self.context.update();
+
+            for (i, sample) in block.into_iter().enumerate() {
+                let filtered = self.downsampler.process(sample);
+                let index = chunk * BLOCK_SIZE + i;
+
+                if index.is_multiple_of(self.factor) {
+                    output[index / self.factor] = filtered;
+                }
+            }
+        }
+
+        output
+    }
+}
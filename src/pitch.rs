@@ -0,0 +1,144 @@
+use std::f32::consts::PI;
+
+use crate::delay::DelayLine;
+use crate::delay::Interpolation;
+use crate::Block;
+use crate::Operator;
+use crate::SynthContext;
+
+const SHIFT_WINDOW: f32 = 0.05;
+
+const STRETCH_FRAME: f32 = 0.04;
+
+#[derive(Debug, Clone)]
+pub struct PitchShift<I, Cv> {
+    input: I,
+    shift: Cv,
+    window: f32,
+    line: DelayLine,
+    phase: f32,
+}
+
+impl<I, Cv> PitchShift<I, Cv>
+where
+    I: Operator,
+    Cv: Operator,
+{
+    pub fn new(input: I, shift: Cv) -> Self {
+        Self {
+            input,
+            shift,
+            window: SHIFT_WINDOW,
+            line: DelayLine::new(),
+            phase: 0.0,
+        }
+    }
+
+    pub fn window(self, window: f32) -> Self {
+        Self { window, ..self }
+    }
+}
+
+impl<I, Cv> Operator for PitchShift<I, Cv>
+where
+    I: Operator,
+    Cv: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let shift = self.shift.render(context);
+
+        let window = (self.window * context.sample_rate() as f32).max(2.0);
+        self.line.allocate(self.window, context.sample_rate());
+
+        Block::from_sample_fn(|i| {
+            // Two taps sweep through the delay line at a rate that resamples the input by the
+            // shift ratio, each fading out as it wraps while the other covers for it.
+            let ratio = crate::volt_octave(1.0, shift[i]);
+            self.phase = (self.phase + (1.0 - ratio) / window).rem_euclid(1.0);
+
+            let output = [self.phase, (self.phase + 0.5) % 1.0]
+                .into_iter()
+                .map(|phase| {
+                    let gain = (PI * phase).sin().powi(2);
+                    let sample = self.line.interpolate(phase * window, Interpolation::Cubic);
+
+                    sample * gain
+                })
+                .sum();
+
+            self.line.write(input[i]);
+            output
+        })
+    }
+}
+
+fn hann(length: usize) -> Vec<f32> {
+    (0..length)
+        .map(|i| (PI * i as f32 / length as f32).sin().powi(2))
+        .collect()
+}
+
+// Stretches a buffer by the given factor without changing its pitch, using
+// waveform-similarity overlap-add (WSOLA). Factors above 1 make the buffer longer.
+pub fn time_stretch(samples: &[f32], factor: f32, sample_rate: u32) -> Vec<f32> {
+    let factor = factor.max(f32::EPSILON);
+    let output_length = (samples.len() as f32 * factor).round() as usize;
+
+    let frame = ((STRETCH_FRAME * sample_rate as f32) as usize).max(4);
+    let synthesis_hop = frame / 2;
+    let analysis_hop = synthesis_hop as f32 / factor;
+    let tolerance = synthesis_hop / 2;
+
+    let window = hann(frame);
+    let input = |index: isize| {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| samples.get(index))
+            .copied()
+            .unwrap_or(0.0)
+    };
+
+    let mut output = vec![0.0; output_length + frame];
+    let mut normalisation = vec![0.0; output_length + frame];
+    let mut previous: Option<isize> = None;
+
+    for (k, output_position) in (0..output_length).step_by(synthesis_hop).enumerate() {
+        let nominal = (k as f32 * analysis_hop).round() as isize;
+
+        // Pick the frame near its nominal position that best continues the previous one.
+        let position = match previous {
+            None => nominal,
+            Some(previous) => {
+                let natural = previous + synthesis_hop as isize;
+
+                (-(tolerance as isize)..=tolerance as isize)
+                    .map(|offset| {
+                        let candidate = nominal + offset;
+                        let correlation = (0..frame as isize)
+                            .map(|j| input(candidate + j) * input(natural + j))
+                            .sum::<f32>();
+
+                        (candidate, correlation)
+                    })
+                    .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                    .map(|(candidate, _)| candidate)
+                    .unwrap_or(nominal)
+            }
+        };
+
+        for (j, gain) in window.iter().enumerate() {
+            output[output_position + j] += input(position + j as isize) * gain;
+            normalisation[output_position + j] += gain;
+        }
+
+        previous = Some(position);
+    }
+
+    output
+        .into_iter()
+        .zip(normalisation)
+        .take(output_length)
+        .map(|(sample, gain)| if gain > 1e-3 { sample / gain } else { sample })
+        .collect()
+}
@@ -0,0 +1,142 @@
+use crate::delay::DelayLine;
+use crate::Block;
+use crate::Lerp;
+use crate::Operator;
+use crate::SynthContext;
+
+// Freeverb's comb and allpass tunings, in seconds at its original 44.1kHz sample rate.
+const COMB_TIMES: [f32; 8] = [
+    1116.0 / 44100.0,
+    1188.0 / 44100.0,
+    1277.0 / 44100.0,
+    1356.0 / 44100.0,
+    1422.0 / 44100.0,
+    1491.0 / 44100.0,
+    1557.0 / 44100.0,
+    1617.0 / 44100.0,
+];
+
+const ALLPASS_TIMES: [f32; 4] = [
+    556.0 / 44100.0,
+    441.0 / 44100.0,
+    341.0 / 44100.0,
+    225.0 / 44100.0,
+];
+
+const ALLPASS_FEEDBACK: f32 = 0.5;
+const INPUT_GAIN: f32 = 0.2;
+const MIN_SIZE: f32 = 0.5;
+const MAX_SIZE: f32 = 1.5;
+const MAX_PRE_DELAY: f32 = 0.5;
+
+#[derive(Debug, Clone, Default)]
+struct DampedComb {
+    line: DelayLine,
+    filtered: f32,
+}
+
+impl DampedComb {
+    fn process(&mut self, input: f32, delay: f32, feedback: f32, damping: f32) -> f32 {
+        let output = self.line.read(delay);
+        self.filtered = output.lerp(self.filtered, damping);
+
+        self.line.write(input + self.filtered * feedback);
+        output
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Reverb<I, S, D, Dp, P, M> {
+    input: I,
+    size: S,
+    decay: D,
+    damping: Dp,
+    pre_delay: P,
+    mix: M,
+    pre_delay_line: DelayLine,
+    combs: [DampedComb; 8],
+    allpasses: [DelayLine; 4],
+}
+
+impl<I, S, D, Dp, P, M> Reverb<I, S, D, Dp, P, M>
+where
+    I: Operator,
+    S: Operator,
+    D: Operator,
+    Dp: Operator,
+    P: Operator,
+    M: Operator,
+{
+    pub fn new(input: I, size: S, decay: D, damping: Dp, pre_delay: P, mix: M) -> Self {
+        Self {
+            input,
+            size,
+            decay,
+            damping,
+            pre_delay,
+            mix,
+            pre_delay_line: DelayLine::new(),
+            combs: Default::default(),
+            allpasses: Default::default(),
+        }
+    }
+}
+
+impl<I, S, D, Dp, P, M> Operator for Reverb<I, S, D, Dp, P, M>
+where
+    I: Operator,
+    S: Operator,
+    D: Operator,
+    Dp: Operator,
+    P: Operator,
+    M: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let size = self.size.render(context);
+        let decay = self.decay.render(context);
+        let damping = self.damping.render(context);
+        let pre_delay = self.pre_delay.render(context);
+        let mix = self.mix.render(context);
+
+        let sample_rate = context.sample_rate();
+        let samples = |time: f32| time * sample_rate as f32;
+
+        self.pre_delay_line.allocate(MAX_PRE_DELAY, sample_rate);
+
+        for (comb, time) in self.combs.iter_mut().zip(COMB_TIMES) {
+            comb.line.allocate(time * MAX_SIZE, sample_rate);
+        }
+
+        for (allpass, time) in self.allpasses.iter_mut().zip(ALLPASS_TIMES) {
+            allpass.allocate(time * MAX_SIZE, sample_rate);
+        }
+
+        Block::from_sample_fn(|i| {
+            let size = MIN_SIZE.lerp(MAX_SIZE, size[i]);
+            let feedback = 0.7.lerp(0.98, decay[i]);
+            let damping = damping[i].clamp(0.0, 1.0) * 0.4;
+
+            let pre_delay = samples(pre_delay[i].clamp(0.0, MAX_PRE_DELAY));
+            let delayed = self.pre_delay_line.read(pre_delay);
+            self.pre_delay_line.write(input[i] * INPUT_GAIN);
+
+            let mut wet = self
+                .combs
+                .iter_mut()
+                .zip(COMB_TIMES)
+                .map(|(comb, time)| comb.process(delayed, samples(time * size), feedback, damping))
+                .sum::<f32>();
+
+            for (allpass, time) in self.allpasses.iter_mut().zip(ALLPASS_TIMES) {
+                let delayed = allpass.read(samples(time * size));
+                let stored = wet + ALLPASS_FEEDBACK * delayed;
+
+                allpass.write(stored);
+                wet = delayed - ALLPASS_FEEDBACK * stored;
+            }
+
+            input[i].lerp(wet, mix[i])
+        })
+    }
+}
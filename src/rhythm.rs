@@ -0,0 +1,329 @@
+use crate::detect::Trigger;
+use crate::detect::TriggerState;
+use crate::sources::Silence;
+use crate::Block;
+use crate::Operator;
+use crate::OperatorExt;
+use crate::SynthContext;
+
+fn high(sample: f32) -> bool {
+    TriggerState::from(sample) == TriggerState::High
+}
+
+fn whole(sample: f32) -> usize {
+    sample.round().max(1.0) as usize
+}
+
+#[derive(Debug, Clone)]
+pub struct Divider<I, N, R> {
+    input: Trigger<I>,
+    division: N,
+    reset: Trigger<R>,
+    count: usize,
+}
+
+impl<I, N> Divider<I, N, Silence>
+where
+    I: Operator,
+    N: Operator,
+{
+    pub fn new(input: I, division: N) -> Self {
+        Self {
+            input: input.trigger(),
+            division,
+            reset: Silence.trigger(),
+            count: 0,
+        }
+    }
+}
+
+impl<I, N, R> Divider<I, N, R> {
+    pub fn reset<T>(self, reset: T) -> Divider<I, N, T>
+    where
+        T: Operator,
+    {
+        Divider {
+            input: self.input,
+            division: self.division,
+            reset: reset.trigger(),
+            count: self.count,
+        }
+    }
+}
+
+impl<I, N, R> Operator for Divider<I, N, R>
+where
+    I: Operator,
+    N: Operator,
+    R: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let division = self.division.render(context);
+        let reset = self.reset.render(context);
+
+        Block::from_sample_fn(|i| {
+            if high(reset[i]) {
+                self.count = 0;
+            }
+
+            if !high(input[i]) {
+                return 0.0;
+            }
+
+            // The first trigger after a reset always passes, so divisions stay aligned.
+            let ticked = self.count.is_multiple_of(whole(division[i]));
+            self.count += 1;
+
+            if ticked {
+                1.0
+            } else {
+                0.0
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Multiplier<I, N, R> {
+    input: Trigger<I>,
+    multiplication: N,
+    reset: Trigger<R>,
+    period: Option<u64>,
+    elapsed: Option<u64>,
+    emitted: usize,
+}
+
+impl<I, N> Multiplier<I, N, Silence>
+where
+    I: Operator,
+    N: Operator,
+{
+    pub fn new(input: I, multiplication: N) -> Self {
+        Self {
+            input: input.trigger(),
+            multiplication,
+            reset: Silence.trigger(),
+            period: None,
+            elapsed: None,
+            emitted: 0,
+        }
+    }
+}
+
+impl<I, N, R> Multiplier<I, N, R> {
+    pub fn reset<T>(self, reset: T) -> Multiplier<I, N, T>
+    where
+        T: Operator,
+    {
+        Multiplier {
+            input: self.input,
+            multiplication: self.multiplication,
+            reset: reset.trigger(),
+            period: self.period,
+            elapsed: self.elapsed,
+            emitted: self.emitted,
+        }
+    }
+}
+
+impl<I, N, R> Operator for Multiplier<I, N, R>
+where
+    I: Operator,
+    N: Operator,
+    R: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let multiplication = self.multiplication.render(context);
+        let reset = self.reset.render(context);
+
+        Block::from_sample_fn(|i| {
+            // A reset forgets the measured period, so the next two input triggers measure it again.
+            if high(reset[i]) {
+                self.period = None;
+                self.elapsed = None;
+            }
+
+            if high(input[i]) {
+                if let Some(elapsed) = self.elapsed {
+                    self.period = Some(elapsed);
+                }
+
+                self.elapsed = Some(1);
+                self.emitted = 1;
+                return 1.0;
+            }
+
+            let Some(elapsed) = self.elapsed.as_mut() else {
+                return 0.0;
+            };
+
+            let position = *elapsed;
+            *elapsed += 1;
+
+            let multiplication = whole(multiplication[i]);
+
+            // Subdivisions are placed against the last measured period and stop once it has
+            // passed, so a slowing clock never gets extra ticks.
+            match self.period {
+                Some(period)
+                    if self.emitted < multiplication
+                        && position * multiplication as u64 >= self.emitted as u64 * period =>
+                {
+                    self.emitted += 1;
+                    1.0
+                }
+                _ => 0.0,
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Counter<I, N, R> {
+    input: Trigger<I>,
+    length: N,
+    reset: Trigger<R>,
+    count: usize,
+}
+
+impl<I, N> Counter<I, N, Silence>
+where
+    I: Operator,
+    N: Operator,
+{
+    pub fn new(input: I, length: N) -> Self {
+        Self {
+            input: input.trigger(),
+            length,
+            reset: Silence.trigger(),
+            count: 0,
+        }
+    }
+}
+
+impl<I, N, R> Counter<I, N, R> {
+    pub fn reset<T>(self, reset: T) -> Counter<I, N, T>
+    where
+        T: Operator,
+    {
+        Counter {
+            input: self.input,
+            length: self.length,
+            reset: reset.trigger(),
+            count: self.count,
+        }
+    }
+}
+
+impl<I, N, R> Operator for Counter<I, N, R>
+where
+    I: Operator,
+    N: Operator,
+    R: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let length = self.length.render(context);
+        let reset = self.reset.render(context);
+
+        Block::from_sample_fn(|i| {
+            if high(reset[i]) {
+                self.count = 0;
+            } else if high(input[i]) {
+                self.count += 1;
+            }
+
+            // Wrapping on read lets the length shrink and grow without losing the position.
+            (self.count % whole(length[i])) as f32
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Euclidean<I, S, P, Ro, R> {
+    input: Trigger<I>,
+    steps: S,
+    pulses: P,
+    rotation: Ro,
+    reset: Trigger<R>,
+    count: usize,
+}
+
+impl<I, S, P, Ro> Euclidean<I, S, P, Ro, Silence>
+where
+    I: Operator,
+    S: Operator,
+    P: Operator,
+    Ro: Operator,
+{
+    pub fn new(input: I, steps: S, pulses: P, rotation: Ro) -> Self {
+        Self {
+            input: input.trigger(),
+            steps,
+            pulses,
+            rotation,
+            reset: Silence.trigger(),
+            count: 0,
+        }
+    }
+}
+
+impl<I, S, P, Ro, R> Euclidean<I, S, P, Ro, R> {
+    pub fn reset<T>(self, reset: T) -> Euclidean<I, S, P, Ro, T>
+    where
+        T: Operator,
+    {
+        Euclidean {
+            input: self.input,
+            steps: self.steps,
+            pulses: self.pulses,
+            rotation: self.rotation,
+            reset: reset.trigger(),
+            count: self.count,
+        }
+    }
+}
+
+impl<I, S, P, Ro, R> Operator for Euclidean<I, S, P, Ro, R>
+where
+    I: Operator,
+    S: Operator,
+    P: Operator,
+    Ro: Operator,
+    R: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let steps = self.steps.render(context);
+        let pulses = self.pulses.render(context);
+        let rotation = self.rotation.render(context);
+        let reset = self.reset.render(context);
+
+        Block::from_sample_fn(|i| {
+            if high(reset[i]) {
+                self.count = 0;
+            }
+
+            if !high(input[i]) {
+                return 0.0;
+            }
+
+            let steps = whole(steps[i]);
+            let pulses = (pulses[i].round().max(0.0) as usize).min(steps);
+            let rotation = rotation[i].round().max(0.0) as usize % steps;
+
+            let step = (self.count % steps + steps - rotation) % steps;
+            self.count += 1;
+
+            // Bresenham-style spacing gives the same patterns as Bjorklund's algorithm, up to
+            // rotation, with the first step always on a pulse.
+            if (step * pulses) % steps < pulses {
+                1.0
+            } else {
+                0.0
+            }
+        })
+    }
+}
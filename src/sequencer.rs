@@ -0,0 +1,401 @@
+use crate::branch::MultiOperator;
+use crate::detect::Trigger;
+use crate::detect::TriggerState;
+use crate::slew::slew_step;
+use crate::slew::SlewMode;
+use crate::sources::Const;
+use crate::sources::Silence;
+use crate::Block;
+use crate::Operator;
+use crate::OperatorExt;
+use crate::SynthContext;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Step {
+    pub pitch: f32,
+    pub gate: f32,
+    pub velocity: f32,
+    pub probability: f32,
+    pub ratchets: usize,
+    pub slide: bool,
+    pub skip: bool,
+}
+
+impl Step {
+    pub fn new(pitch: f32) -> Self {
+        Self {
+            pitch,
+            gate: 0.5,
+            velocity: 1.0,
+            probability: 1.0,
+            ratchets: 1,
+            slide: false,
+            skip: false,
+        }
+    }
+
+    pub fn gate(self, gate: f32) -> Self {
+        Self { gate, ..self }
+    }
+
+    pub fn velocity(self, velocity: f32) -> Self {
+        Self { velocity, ..self }
+    }
+
+    pub fn probability(self, probability: f32) -> Self {
+        Self {
+            probability,
+            ..self
+        }
+    }
+
+    pub fn ratchets(self, ratchets: usize) -> Self {
+        Self {
+            ratchets: ratchets.max(1),
+            ..self
+        }
+    }
+
+    pub fn slide(self) -> Self {
+        Self {
+            slide: true,
+            ..self
+        }
+    }
+
+    pub fn skip(self) -> Self {
+        Self { skip: true, ..self }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    #[default]
+    Forward,
+    Reverse,
+    PingPong,
+    Random,
+}
+
+#[derive(Debug, Clone)]
+pub struct StepSequencer<C, G, R> {
+    clock: Trigger<C>,
+    glide: G,
+    reset: Trigger<R>,
+    steps: Vec<Step>,
+    direction: Direction,
+    ascending: bool,
+    index: Option<usize>,
+    playing: bool,
+    gated: bool,
+    period: Option<u64>,
+    elapsed: Option<u64>,
+    pitch: f32,
+}
+
+impl<C> StepSequencer<C, Const, Silence>
+where
+    C: Operator,
+{
+    pub fn new(clock: C, steps: impl IntoIterator<Item = Step>) -> Self {
+        let steps: Vec<_> = steps.into_iter().collect();
+        let pitch = steps.first().map_or(0.0, |step| step.pitch);
+
+        Self {
+            clock: clock.trigger(),
+            glide: Const(0.05),
+            reset: Silence.trigger(),
+            steps,
+            direction: Direction::default(),
+            ascending: true,
+            index: None,
+            playing: false,
+            gated: false,
+            period: None,
+            elapsed: None,
+            pitch,
+        }
+    }
+}
+
+impl<C, G, R> StepSequencer<C, G, R> {
+    pub fn direction(self, direction: Direction) -> Self {
+        Self { direction, ..self }
+    }
+
+    pub fn glide<Cv>(self, glide: Cv) -> StepSequencer<C, Cv, R>
+    where
+        Cv: Operator,
+    {
+        StepSequencer {
+            clock: self.clock,
+            glide,
+            reset: self.reset,
+            steps: self.steps,
+            direction: self.direction,
+            ascending: self.ascending,
+            index: self.index,
+            playing: self.playing,
+            gated: self.gated,
+            period: self.period,
+            elapsed: self.elapsed,
+            pitch: self.pitch,
+        }
+    }
+
+    pub fn reset<T>(self, reset: T) -> StepSequencer<C, G, T>
+    where
+        T: Operator,
+    {
+        StepSequencer {
+            clock: self.clock,
+            glide: self.glide,
+            reset: reset.trigger(),
+            steps: self.steps,
+            direction: self.direction,
+            ascending: self.ascending,
+            index: self.index,
+            playing: self.playing,
+            gated: self.gated,
+            period: self.period,
+            elapsed: self.elapsed,
+            pitch: self.pitch,
+        }
+    }
+
+    fn playable(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.steps.len()).filter(|&index| !self.steps[index].skip)
+    }
+
+    // Walks to the next step that isn't skipped, wrapping around the ends.
+    fn next_playable(&self, index: usize, ascending: bool) -> usize {
+        let length = self.steps.len();
+
+        (1..=length)
+            .map(|offset| {
+                if ascending {
+                    (index + offset) % length
+                } else {
+                    (index + length - offset) % length
+                }
+            })
+            .find(|&index| !self.steps[index].skip)
+            .unwrap_or(index)
+    }
+
+    fn advance(&mut self) -> Option<usize> {
+        let first = self.playable().next()?;
+        let last = self.playable().last()?;
+
+        let next = match (self.direction, self.index) {
+            (Direction::Random, _) => {
+                let choice = rand::random::<usize>() % self.playable().count();
+                self.playable().nth(choice)?
+            }
+            (Direction::Forward | Direction::PingPong, None) => first,
+            (Direction::Reverse, None) => last,
+            (Direction::Forward, Some(index)) => self.next_playable(index, true),
+            (Direction::Reverse, Some(index)) => self.next_playable(index, false),
+            (Direction::PingPong, Some(index)) => {
+                // Turning at the outermost playable steps plays each end once per bounce.
+                if self.ascending && index >= last || !self.ascending && index <= first {
+                    self.ascending = !self.ascending;
+                }
+
+                self.next_playable(index, self.ascending)
+            }
+        };
+
+        Some(next)
+    }
+
+    fn gate(&self, step: &Step, elapsed: u64) -> bool {
+        if !self.playing {
+            return false;
+        }
+
+        // Until two clocks have arrived there is no step length to divide, so the gate
+        // simply stays open for the first step.
+        let Some(period) = self.period else {
+            return true;
+        };
+
+        let ratchet_length = period as f32 / step.ratchets as f32;
+        let position = elapsed as f32 / ratchet_length;
+        let ratchet = position.floor() as usize;
+
+        if ratchet < step.ratchets {
+            position.fract() < step.gate
+        } else {
+            step.gate >= 1.0
+        }
+    }
+}
+
+impl<C, G, R> MultiOperator<3> for StepSequencer<C, G, R>
+where
+    C: Operator,
+    G: Operator,
+    R: Operator,
+{
+    fn render_outputs(&mut self, context: &mut SynthContext) -> [Block; 3] {
+        let clock = self.clock.render(context);
+        let glide = self.glide.render(context);
+        let reset = self.reset.render(context);
+
+        let mut gate = Block::silence();
+        let mut velocity = Block::silence();
+
+        if self.steps.is_empty() {
+            return [Block::silence(), gate, velocity];
+        }
+
+        let pitch = Block::from_sample_fn(|i| {
+            // A reset arms the sequencer, so the next clock plays the first step again.
+            if TriggerState::from(reset[i]) == TriggerState::High {
+                self.index = None;
+                self.ascending = true;
+                self.playing = false;
+            }
+
+            let mut retrigger = false;
+
+            if TriggerState::from(clock[i]) == TriggerState::High {
+                // An open gate closes for a sample between steps so envelopes retrigger,
+                // unless the step being left is tied into the next one.
+                let tied = self
+                    .index
+                    .is_some_and(|index| self.steps[index].gate >= 1.0);
+                retrigger = self.gated && !tied;
+
+                if let Some(elapsed) = self.elapsed {
+                    self.period = Some(elapsed);
+                }
+
+                self.elapsed = Some(0);
+                self.index = self.advance();
+
+                if let Some(index) = self.index {
+                    let step = self.steps[index];
+
+                    self.playing = rand::random::<f32>() < step.probability;
+
+                    // A sliding step glides in from the previous pitch instead of jumping.
+                    if !step.slide {
+                        self.pitch = step.pitch;
+                    }
+                }
+            }
+
+            let elapsed = self.elapsed.unwrap_or_default();
+            self.elapsed = self.elapsed.map(|elapsed| elapsed + 1);
+
+            let Some(index) = self.index else {
+                self.gated = false;
+                return self.pitch;
+            };
+
+            let step = self.steps[index];
+            self.pitch = slew_step(
+                self.pitch,
+                step.pitch,
+                glide[i],
+                context.sample_time(),
+                SlewMode::Exponential,
+            );
+
+            self.gated = !retrigger && self.gate(&step, elapsed);
+
+            gate[i] = if self.gated { 1.0 } else { 0.0 };
+            velocity[i] = step.velocity;
+
+            self.pitch
+        });
+
+        [pitch, gate, velocity]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::impulse_response;
+    use crate::Clock;
+
+    fn rising_edges(steps: impl IntoIterator<Item = Step>) -> Vec<usize> {
+        let [_, gate, _] = Clock::bpm(120.0).step_sequencer(steps).split();
+        let gate = impulse_response(48_000, 120_000, |_| gate);
+
+        (1..gate.len())
+            .filter(|&i| gate[i] > 0.0 && gate[i - 1] == 0.0)
+            .collect()
+    }
+
+    fn order(steps: impl IntoIterator<Item = Step>, direction: Direction) -> Vec<usize> {
+        let mut sequencer = StepSequencer::new(Silence, steps).direction(direction);
+
+        (0..8)
+            .map(|_| {
+                sequencer.index = sequencer.advance();
+                sequencer.index.unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ping_pong_turns_at_playable_ends() {
+        let steps = [
+            Step::new(0.0),
+            Step::new(1.0).skip(),
+            Step::new(2.0),
+            Step::new(3.0).skip(),
+        ];
+
+        assert_eq!(
+            order([0.0, 1.0, 2.0, 3.0].map(Step::new), Direction::PingPong),
+            [0, 1, 2, 3, 2, 1, 0, 1]
+        );
+        assert_eq!(order(steps, Direction::PingPong), [0, 2, 0, 2, 0, 2, 0, 2]);
+        assert_eq!(order(steps, Direction::Reverse), [2, 0, 2, 0, 2, 0, 2, 0]);
+    }
+
+    #[test]
+    fn random_picks_evenly_between_playable_steps() {
+        let steps = [
+            Step::new(0.0),
+            Step::new(1.0).skip(),
+            Step::new(2.0).skip(),
+            Step::new(3.0),
+        ];
+        let mut sequencer = StepSequencer::new(Silence, steps).direction(Direction::Random);
+
+        let picks: Vec<_> = (0..2000).map(|_| sequencer.advance().unwrap()).collect();
+        let first = picks.iter().filter(|&&index| index == 0).count();
+
+        assert!(picks.iter().all(|&index| index == 0 || index == 3));
+        assert!(
+            (800..1200).contains(&first),
+            "first step picked {first} times"
+        );
+    }
+
+    #[test]
+    fn every_step_retriggers_the_gate() {
+        let edges = rising_edges([Step::new(0.0), Step::new(1.0)]);
+        let ticks = (1..=5).map(|tick| tick * 24_000 - 1);
+
+        // The second step opens one sample late, after its retrigger gap.
+        assert_eq!(edges.len(), 5);
+        assert!(edges
+            .iter()
+            .zip(ticks)
+            .all(|(&edge, tick)| edge - tick <= 1));
+    }
+
+    #[test]
+    fn tied_steps_hold_the_gate() {
+        let edges = rising_edges([Step::new(0.0).gate(1.0), Step::new(1.0).gate(1.0)]);
+
+        assert_eq!(edges, [23_999]);
+    }
+}
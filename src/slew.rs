@@ -0,0 +1,87 @@
+use crate::Block;
+use crate::Operator;
+use crate::SynthContext;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlewMode {
+    #[default]
+    Linear,
+    Exponential,
+}
+
+#[derive(Debug, Clone)]
+pub struct Slew<I, R, F> {
+    input: I,
+    rise: R,
+    fall: F,
+    mode: SlewMode,
+    value: Option<f32>,
+}
+
+impl<I, R, F> Slew<I, R, F>
+where
+    I: Operator,
+    R: Operator,
+    F: Operator,
+{
+    pub fn new(input: I, rise: R, fall: F) -> Self {
+        Self {
+            input,
+            rise,
+            fall,
+            mode: SlewMode::default(),
+            value: None,
+        }
+    }
+
+    pub fn mode(self, mode: SlewMode) -> Self {
+        Self { mode, ..self }
+    }
+}
+
+pub(crate) fn slew_step(
+    value: f32,
+    target: f32,
+    time: f32,
+    sample_time: f32,
+    mode: SlewMode,
+) -> f32 {
+    if time <= 0.0 {
+        return target;
+    }
+
+    match mode {
+        SlewMode::Linear => {
+            let step = sample_time / time;
+            value + (target - value).clamp(-step, step)
+        }
+        SlewMode::Exponential => {
+            let coefficient = (-sample_time / time).exp();
+            target + (value - target) * coefficient
+        }
+    }
+}
+
+impl<I, R, F> Operator for Slew<I, R, F>
+where
+    I: Operator,
+    R: Operator,
+    F: Operator,
+{
+    fn render(&mut self, context: &mut SynthContext) -> Block {
+        let input = self.input.render(context);
+        let rise = self.rise.render(context);
+        let fall = self.fall.render(context);
+
+        Block::from_sample_fn(|i| {
+            let target = input[i];
+            let value = self.value.unwrap_or(target);
+
+            let time = if target > value { rise[i] } else { fall[i] };
+            let value = slew_step(value, target, time, context.sample_time(), self.mode);
+
+            self.value = Some(value);
+            value
+        })
+    }
+}
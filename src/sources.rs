@@ -1,7 +1,10 @@
 use std::f32::consts::PI;
 
+use crate::detect::Trigger;
+use crate::detect::TriggerState;
 use crate::Block;
 use crate::Operator;
+use crate::OperatorExt;
 use crate::SynthContext;
 use crate::BLOCK_SIZE;
 
@@ -131,6 +134,25 @@ impl Operator for f32 {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Impulse {
+    fired: bool,
+}
+
+impl Impulse {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Operator for Impulse {
+    fn render(&mut self, _: &mut SynthContext) -> Block {
+        let fired = std::mem::replace(&mut self.fired, true);
+
+        Block::from_sample_fn(|i| if i == 0 && !fired { 1.0 } else { 0.0 })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Oscillator<S> {
     frequency: f32,
@@ -237,89 +259,220 @@ impl Operator for WhiteNoise {
     }
 }
 
+// Tracks beats elapsed since the last tick. Only the fractional remainder carries over
+// between ticks, so the tempo never drifts even though ticks land on whole samples.
 #[derive(Debug, Clone, Copy)]
-pub struct Clock {
-    interval_sec: f32,
-    completed: u32,
+struct Tempo {
+    elapsed: f64,
+    offbeat: bool,
 }
 
-impl Clock {
-    pub fn bpm(bpm: f32) -> Self {
+impl Tempo {
+    fn new() -> Self {
         Self {
-            interval_sec: 60.0 / bpm,
-            completed: 0,
+            elapsed: 0.0,
+            offbeat: true,
         }
     }
+
+    // Swing delays every other tick by up to half an interval.
+    fn interval(&self, swing: f32) -> f64 {
+        let swing = swing.clamp(0.0, 1.0) as f64 * 0.5;
+
+        if self.offbeat {
+            1.0 + swing
+        } else {
+            1.0 - swing
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn advance(&mut self, bpm: f32, swing: f32, sample_rate: u32) -> bool {
+        self.elapsed += bpm.max(0.0) as f64 / 60.0 / sample_rate as f64;
+
+        let interval = self.interval(swing);
+
+        if self.elapsed >= interval {
+            self.elapsed -= interval;
+            self.offbeat = !self.offbeat;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Clock<B, S, R> {
+    bpm: B,
+    swing: S,
+    reset: Trigger<R>,
+    tempo: Tempo,
 }
 
-impl Operator for Clock {
+impl<B> Clock<B, Const, Silence>
+where
+    B: Operator,
+{
+    pub fn bpm(bpm: B) -> Self {
+        Self {
+            bpm,
+            swing: Const(0.0),
+            reset: Silence.trigger(),
+            tempo: Tempo::new(),
+        }
+    }
+}
+
+impl<B, S, R> Clock<B, S, R> {
+    pub fn swing<Cv>(self, swing: Cv) -> Clock<B, Cv, R>
+    where
+        Cv: Operator,
+    {
+        Clock {
+            bpm: self.bpm,
+            swing,
+            reset: self.reset,
+            tempo: self.tempo,
+        }
+    }
+
+    pub fn reset<T>(self, reset: T) -> Clock<B, S, T>
+    where
+        T: Operator,
+    {
+        Clock {
+            bpm: self.bpm,
+            swing: self.swing,
+            reset: reset.trigger(),
+            tempo: self.tempo,
+        }
+    }
+}
+
+impl<B, S, R> Operator for Clock<B, S, R>
+where
+    B: Operator,
+    S: Operator,
+    R: Operator,
+{
     fn render(&mut self, context: &mut SynthContext) -> Block {
-        let interval = (self.interval_sec * context.sample_rate as f32).ceil() as u32;
+        let bpm = self.bpm.render(context);
+        let swing = self.swing.render(context);
+        let reset = self.reset.render(context);
 
-        Block::from_sample_fn(|_| {
-            if self.completed == interval {
-                self.completed = 0;
+        Block::from_sample_fn(|i| {
+            let ticked = if TriggerState::from(reset[i]) == TriggerState::High {
+                self.tempo.reset();
+                true
+            } else {
+                self.tempo.advance(bpm[i], swing[i], context.sample_rate())
+            };
+
+            if ticked {
                 1.0
             } else {
-                self.completed += 1;
                 0.0
             }
         })
     }
 }
 
-pub struct Gate<Cv> {
-    interval_sec: f32,
-    completed: u32,
-    width: Cv,
+#[derive(Debug, Clone)]
+pub struct Gate<B, W, S, R> {
+    bpm: B,
+    width: W,
+    swing: S,
+    reset: Trigger<R>,
+    tempo: Tempo,
 }
 
-impl Gate<Const> {
-    pub fn bpm(bpm: f32) -> Self {
+impl<B> Gate<B, Const, Const, Silence>
+where
+    B: Operator,
+{
+    pub fn bpm(bpm: B) -> Self {
         Self {
-            interval_sec: 60.0 / bpm,
-            completed: 0,
+            bpm,
             width: Const(0.5),
+            swing: Const(0.0),
+            reset: Silence.trigger(),
+            tempo: Tempo::new(),
         }
     }
 }
 
-impl<Cv> Gate<Cv>
-where
-    Cv: Operator,
-{
-    pub fn width(self, input: Cv) -> Self {
-        Self {
-            interval_sec: self.interval_sec,
-            completed: self.completed,
-            width: input,
+impl<B, W, S, R> Gate<B, W, S, R> {
+    pub fn width<Cv>(self, width: Cv) -> Gate<B, Cv, S, R>
+    where
+        Cv: Operator,
+    {
+        Gate {
+            bpm: self.bpm,
+            width,
+            swing: self.swing,
+            reset: self.reset,
+            tempo: self.tempo,
+        }
+    }
+
+    pub fn swing<Cv>(self, swing: Cv) -> Gate<B, W, Cv, R>
+    where
+        Cv: Operator,
+    {
+        Gate {
+            bpm: self.bpm,
+            width: self.width,
+            swing,
+            reset: self.reset,
+            tempo: self.tempo,
+        }
+    }
+
+    pub fn reset<T>(self, reset: T) -> Gate<B, W, S, T>
+    where
+        T: Operator,
+    {
+        Gate {
+            bpm: self.bpm,
+            width: self.width,
+            swing: self.swing,
+            reset: reset.trigger(),
+            tempo: self.tempo,
         }
     }
 }
 
-impl<Cv> Operator for Gate<Cv>
+impl<B, W, S, R> Operator for Gate<B, W, S, R>
 where
-    Cv: Operator,
+    B: Operator,
+    W: Operator,
+    S: Operator,
+    R: Operator,
 {
     fn render(&mut self, context: &mut SynthContext) -> Block {
-        let interval = (self.interval_sec * context.sample_rate as f32).ceil() as u32;
+        let bpm = self.bpm.render(context);
         let width = self.width.render(context);
+        let swing = self.swing.render(context);
+        let reset = self.reset.render(context);
 
         Block::from_sample_fn(|i| {
-            let width_sec = width[i] * self.interval_sec;
-            let width = (width_sec * context.sample_rate as f32).ceil() as u32;
+            if TriggerState::from(reset[i]) == TriggerState::High {
+                self.tempo.reset();
+            } else {
+                self.tempo.advance(bpm[i], swing[i], context.sample_rate());
+            }
 
-            let sample = if self.completed == interval {
-                self.completed = 0;
-                1.0
-            } else if self.completed < width {
+            let width = width[i].clamp(0.0, 1.0) as f64 * self.tempo.interval(swing[i]);
+
+            if self.tempo.elapsed < width {
                 1.0
             } else {
                 0.0
-            };
-
-            self.completed += 1;
-            sample
+            }
         })
     }
 }